@@ -0,0 +1,27 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tuxs_config_types::size_config::ConfigSize;
+
+fn size(c: &mut Criterion) {
+    let size = ConfigSize::from_str("512MiB").unwrap();
+    let bits = ConfigSize::from_str("512Mib").unwrap();
+    c.bench_function("size get_as_bytes", |b| {
+        b.iter(|| black_box(size).get_as_bytes())
+    });
+    c.bench_function("size get_as_bytes from bits", |b| {
+        b.iter(|| black_box(bits).get_as_bytes())
+    });
+    c.bench_function("size as_parts", |b| {
+        b.iter(|| black_box(ConfigSize::new_from_bytes(1024 * 1024 * 3)).as_parts())
+    });
+    c.bench_function("size from byte count", |b| {
+        b.iter(|| ConfigSize::from(black_box(1024 * 1024 * 3)))
+    });
+    c.bench_function("size human_readable", |b| {
+        b.iter(|| black_box(size).human_readable())
+    });
+}
+
+criterion_group!(benches, size);
+criterion_main!(benches);
@@ -0,0 +1,76 @@
+//! The `size parsers` group runs the regex parser and the `fast-parse` parser side by side
+//!
+//! ```sh
+//! cargo bench --bench parse -- "size parsers"
+//! ```
+//!
+//! The regex is built once and kept for the life of the process. So the cold benchmarks measure building it
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+#[cfg(feature = "regex")]
+use tuxs_config_types::config_unit::ConfigUnit;
+#[cfg(feature = "regex")]
+use tuxs_config_types::size_config;
+use tuxs_config_types::size_config::ConfigSize;
+
+fn parse_size(c: &mut Criterion) {
+    // Make sure the warm benchmarks never pay for building the regex
+    ConfigSize::from_str("0").unwrap();
+    c.bench_function("parse size", |b| {
+        b.iter(|| ConfigSize::from_str(black_box("512MiB")).unwrap())
+    });
+    c.bench_function("parse size without unit", |b| {
+        b.iter(|| ConfigSize::from_str(black_box("1024")).unwrap())
+    });
+    c.bench_function("parse invalid size", |b| {
+        b.iter(|| ConfigSize::from_str(black_box("512XB")).unwrap_err())
+    });
+    #[cfg(feature = "regex")]
+    c.bench_function("parse size cold", |b| {
+        b.iter(|| {
+            size_config::Unit::build_regex()
+                .captures(black_box("512MiB"))
+                .is_some()
+        })
+    });
+}
+fn compare_size_parsers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("size parsers");
+    for input in ["512MiB", "1024", "512XB"] {
+        #[cfg(feature = "regex")]
+        {
+            ConfigSize::from_str_with_regex("0").unwrap();
+            group.bench_with_input(BenchmarkId::new("regex", input), input, |b, input| {
+                b.iter(|| ConfigSize::from_str_with_regex(black_box(input)))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("fast-parse", input), input, |b, input| {
+            b.iter(|| ConfigSize::from_str_by_hand(black_box(input)))
+        });
+    }
+    group.finish();
+}
+#[cfg(feature = "chrono")]
+fn parse_duration(c: &mut Criterion) {
+    use tuxs_config_types::chrono_types::duration::{self, ConfigDuration};
+    ConfigDuration::from_str("0").unwrap();
+    c.bench_function("parse duration", |b| {
+        b.iter(|| ConfigDuration::from_str(black_box("30s")).unwrap())
+    });
+    c.bench_function("parse invalid duration", |b| {
+        b.iter(|| ConfigDuration::from_str(black_box("30x")).unwrap_err())
+    });
+    c.bench_function("parse duration cold", |b| {
+        b.iter(|| {
+            duration::Unit::build_regex()
+                .captures(black_box("30s"))
+                .is_some()
+        })
+    });
+}
+#[cfg(not(feature = "chrono"))]
+fn parse_duration(_: &mut Criterion) {}
+
+criterion_group!(benches, parse_size, compare_size_parsers, parse_duration);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde::{Deserialize, Serialize};
+use tuxs_config_types::size_config::ConfigSize;
+
+#[derive(Serialize, Deserialize)]
+struct Config {
+    max_upload: ConfigSize,
+    #[cfg(feature = "chrono")]
+    timeout: tuxs_config_types::chrono_types::duration::ConfigDuration,
+}
+fn config() -> Config {
+    Config {
+        max_upload: ConfigSize::from_str("512MiB").unwrap(),
+        #[cfg(feature = "chrono")]
+        timeout: FromStr::from_str("30s").unwrap(),
+    }
+}
+
+fn json(c: &mut Criterion) {
+    let config = config();
+    c.bench_function("json round trip", |b| {
+        b.iter(|| {
+            let json = serde_json::to_string(black_box(&config)).unwrap();
+            serde_json::from_str::<Config>(&json).unwrap()
+        })
+    });
+}
+fn toml(c: &mut Criterion) {
+    let config = config();
+    c.bench_function("toml round trip", |b| {
+        b.iter(|| {
+            let toml = toml::to_string(black_box(&config)).unwrap();
+            toml::from_str::<Config>(&toml).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, json, toml);
+criterion_main!(benches);
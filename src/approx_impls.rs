@@ -0,0 +1,99 @@
+//! [approx](https://docs.rs/approx) support for the config types
+//!
+//! Values are compared by their total quantity, so two values in different units can still be
+//! approximately equal.
+//!
+//! | Type             | Compared By        | Epsilon Unit | Default Epsilon | Default Max Relative | Default Max Ulps |
+//! |------------------|--------------------|--------------|-----------------|----------------------|------------------|
+//! | `ConfigSize`     | Total bytes        | Bytes        | `0.0`           | `f64::EPSILON`       | `4`              |
+//! | `ConfigDuration` | Total milliseconds | Milliseconds | `0.0`           | `f64::EPSILON`       | `4`              |
+//!
+//! ```rust
+//! use tuxs_config_types::size_config::ConfigSize;
+//! use std::str::FromStr;
+//!
+//! let a = ConfigSize::from_str("1KiB").unwrap();
+//! let b = ConfigSize::from_str("1025B").unwrap();
+//! approx::assert_abs_diff_eq!(a, b, epsilon = 1.0);
+//! ```
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use crate::size_config::ConfigSize;
+
+macro_rules! approx_via_f64 {
+    ($type:ty, |$value:ident| $as_f64:expr) => {
+        const _: () = {
+            fn as_f64($value: &$type) -> f64 {
+                $as_f64
+            }
+            impl AbsDiffEq for $type {
+                type Epsilon = f64;
+
+                fn default_epsilon() -> Self::Epsilon {
+                    0.0
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                    as_f64(self).abs_diff_eq(&as_f64(other), epsilon)
+                }
+            }
+            impl RelativeEq for $type {
+                fn default_max_relative() -> Self::Epsilon {
+                    f64::default_max_relative()
+                }
+
+                fn relative_eq(
+                    &self,
+                    other: &Self,
+                    epsilon: Self::Epsilon,
+                    max_relative: Self::Epsilon,
+                ) -> bool {
+                    as_f64(self).relative_eq(&as_f64(other), epsilon, max_relative)
+                }
+            }
+            impl UlpsEq for $type {
+                fn default_max_ulps() -> u32 {
+                    f64::default_max_ulps()
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                    as_f64(self).ulps_eq(&as_f64(other), epsilon, max_ulps)
+                }
+            }
+        };
+    };
+}
+
+approx_via_f64!(ConfigSize, |size| size.get_as_bytes() as f64);
+#[cfg(feature = "chrono")]
+approx_via_f64!(crate::chrono_types::duration::ConfigDuration, |duration| {
+    duration.num_milliseconds() as f64
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_size() {
+        let a = ConfigSize::from_str("1KiB").unwrap();
+        let b = ConfigSize::from_str("1025B").unwrap();
+        approx::assert_abs_diff_eq!(a, b, epsilon = 1.0);
+        approx::assert_abs_diff_ne!(a, b);
+        approx::assert_relative_eq!(a, b, max_relative = 0.001);
+        approx::assert_relative_ne!(a, b);
+        approx::assert_ulps_eq!(a, ConfigSize::from_str("1024B").unwrap());
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        use crate::chrono_types::duration::ConfigDuration;
+        let a = ConfigDuration::from_str("1s").unwrap();
+        let b = ConfigDuration::from_str("1001ms").unwrap();
+        approx::assert_abs_diff_eq!(a, b, epsilon = 1.0);
+        approx::assert_abs_diff_ne!(a, b);
+        approx::assert_relative_eq!(a, b, max_relative = 0.001);
+        approx::assert_ulps_eq!(a, ConfigDuration::from_str("1000ms").unwrap());
+    }
+}
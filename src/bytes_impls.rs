@@ -0,0 +1,52 @@
+//! Allocating [bytes] buffers from a [ConfigSize]
+//!
+//! ```rust
+//! use tuxs_config_types::size_config::ConfigSize;
+//! use std::str::FromStr;
+//!
+//! let buffer_size = ConfigSize::from_str("64KiB").unwrap();
+//! let buffer = buffer_size.allocate_bytes_mut();
+//! assert!(buffer.capacity() >= 64 * 1024);
+//! ```
+use bytes::{Bytes, BytesMut};
+
+use crate::size_config::ConfigSize;
+
+impl ConfigSize {
+    /// An empty [BytesMut] with the capacity of this size in bytes. Bit sizes are rounded down to the whole byte
+    ///
+    /// # Panics
+    /// If the allocation fails. The same as [BytesMut::with_capacity]
+    pub fn allocate_bytes_mut(&self) -> BytesMut {
+        BytesMut::with_capacity(self.get_as_bytes())
+    }
+    /// The length of the buffer in [Unit::Bytes](crate::size_config::Unit::Bytes)
+    pub fn from_bytes_len(buf: &Bytes) -> Self {
+        ConfigSize::new_from_bytes(buf.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::size_config::Unit;
+    use crate::test_helpers::ONE_KIB;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_allocate_bytes_mut() {
+        let buffer = ONE_KIB.allocate_bytes_mut();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 1024);
+        let buffer = ConfigSize::from_str("20b").unwrap().allocate_bytes_mut();
+        assert!(buffer.capacity() >= 2);
+        assert_eq!(ConfigSize::ZERO.allocate_bytes_mut().capacity(), 0);
+    }
+    #[test]
+    pub fn test_from_bytes_len() {
+        let size = ConfigSize::from_bytes_len(&Bytes::from(vec![0u8; 2048]));
+        assert_eq!(size.as_parts(), (2048, Unit::Bytes));
+        assert_eq!(size, ConfigSize::new_from_kibibytes(2));
+        assert!(ConfigSize::from_bytes_len(&Bytes::new()).is_zero());
+    }
+}
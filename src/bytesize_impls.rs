@@ -0,0 +1,43 @@
+//! Conversions between [ConfigSize] and [bytesize::ByteSize]
+//!
+//! Only the byte total is kept. `bytesize` picks its own unit when displaying, with a decimal place (`1.5 MiB`).
+//! While [ConfigSize] keeps the unit it was written with and only accepts whole numbers.
+//! So `ConfigSize::from(ByteSize::kib(1024))` displays as `1MiB`
+use bytesize::ByteSize;
+
+use crate::size_config::ConfigSize;
+
+/// Saturates at `usize::MAX` bytes on targets where `usize` is smaller than `u64`
+impl From<ByteSize> for ConfigSize {
+    fn from(value: ByteSize) -> Self {
+        ConfigSize::from(usize::try_from(value.as_u64()).unwrap_or(usize::MAX))
+    }
+}
+/// Bit sizes are rounded down to the whole byte. Saturates at `u64::MAX` bytes
+impl From<ConfigSize> for ByteSize {
+    fn from(value: ConfigSize) -> Self {
+        ByteSize::b(value.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_round_trip() {
+        for input in ["0B", "10B", "1KiB", "512MiB", "3GiB"] {
+            let size = ConfigSize::from_str(input).unwrap();
+            let byte_size = ByteSize::from(size);
+            assert_eq!(byte_size.as_u64(), size.get_as_bytes() as u64);
+            assert_eq!(ConfigSize::from(byte_size), size);
+        }
+        let size = ConfigSize::from(ByteSize::kib(1024));
+        assert_eq!(size.to_string(), "1MiB");
+        assert_eq!(
+            ByteSize::from(ConfigSize::from_str("12b").unwrap()),
+            ByteSize::b(1)
+        );
+    }
+}
@@ -1,31 +1,78 @@
-use chrono::Duration;
+use chrono::{DateTime, Duration, Utc};
 use derive_more::derive::{AsRef, Deref, DerefMut, From, Into};
 use regex::Regex;
 use std::cmp::Ordering;
 use std::error::Error;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, Range, Sub};
 use std::str::FromStr;
 use std::sync::OnceLock;
 use strum::{
-    AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, IntoEnumIterator, IntoStaticStr,
+    AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumMessage, EnumString, IntoEnumIterator,
+    IntoStaticStr,
 };
 use thiserror::Error;
 
-use crate::macros::{extend_string_from_and_to, serde_via_string_types};
+use crate::config_unit::ConfigUnit;
+use crate::macros::{extend_string_from_and_to, serde_via_display, serde_via_string_types};
 
 static UNITS_REGEX: OnceLock<Regex> = OnceLock::new();
+/// Longer inputs are rejected before reaching the regex. No valid duration comes close
+pub const MAX_INPUT_LEN: usize = 64;
+const SECOND: i64 = 1000;
+const MINUTE: i64 = 60 * SECOND;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
 type AnyError = Box<dyn Error + Send + Sync + 'static>;
+/// Use the accessors such as [InvalidDurationError::context] and [InvalidDurationError::span] to inspect the error.
+/// They are the stable interface. The fields are private and may change
 #[derive(Debug, Error)]
 #[error("{0}: {1:?}")]
-pub struct InvalidDurationError(&'static str, Option<AnyError>);
+#[non_exhaustive]
+pub struct InvalidDurationError(
+    &'static str,
+    Option<AnyError>,
+    Option<Range<usize>>,
+    Option<String>,
+);
 impl From<(&'static str, AnyError)> for InvalidDurationError {
     fn from(value: (&'static str, AnyError)) -> Self {
-        Self(value.0, Some(value.1))
+        Self(value.0, Some(value.1), None, None)
     }
 }
 impl From<&'static str> for InvalidDurationError {
     fn from(value: &'static str) -> Self {
-        Self(value, None)
+        Self(value, None, None, None)
+    }
+}
+impl InvalidDurationError {
+    /// What went wrong. Such as `Unknown unit`
+    pub fn context(&self) -> &str {
+        self.0
+    }
+    /// The underlying error. Such as the integer parse error for a number that is too large
+    pub fn source_error(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.1.as_deref()
+    }
+    /// The byte range of the input that caused the error
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.2.clone()
+    }
+    /// The string that failed to parse. Only set by [FromStr] and only for inputs up to [MAX_INPUT_LEN] bytes
+    pub fn input(&self) -> Option<&str> {
+        self.3.as_deref()
+    }
+    pub(crate) fn with_span(mut self, span: Range<usize>) -> Self {
+        self.2 = Some(span);
+        self
+    }
+    pub(crate) fn with_input(mut self, input: &str) -> Self {
+        if input.len() <= MAX_INPUT_LEN {
+            self.3 = Some(input.to_owned());
+        }
+        self
     }
 }
 #[derive(
@@ -45,48 +92,124 @@ impl From<&'static str> for InvalidDurationError {
     IntoStaticStr,
     EnumIter,
     EnumIs,
+    EnumMessage,
 )]
 #[repr(usize)]
 #[cfg_attr(feature = "digestible", derive(digestible::Digestible))]
 #[non_exhaustive]
 pub enum Unit {
     #[default]
-    #[strum(serialize = "ms")]
+    #[strum(serialize = "ms", message = "Millisecond: 1/1000 of a second")]
     Milliseconds,
-    #[strum(serialize = "s")]
+    #[strum(serialize = "s", message = "Second: 1000 milliseconds")]
     Seconds,
-    #[strum(serialize = "m")]
+    #[strum(serialize = "m", message = "Minute: 60 seconds")]
     Minutes,
-    #[strum(serialize = "h")]
+    #[strum(serialize = "h", message = "Hour: 60 minutes")]
     Hours,
-    #[strum(serialize = "d")]
+    #[strum(serialize = "d", message = "Day: 24 hours")]
     Days,
 }
 serde_via_string_types!(Unit);
 
 impl Unit {
-    pub fn build_regex() -> Regex {
-        Regex::new(&Self::create_regex_string())
-            .map_err(|err| {
-                format!(
-                    "Unable to Build Size Config Regex. Please Report: {:?}. \n{:?}",
-                    Self::create_regex_string(),
-                    err
-                )
-            })
-            .unwrap()
+    /// The number of units. The same as [EnumCount::COUNT]
+    pub const fn count() -> usize {
+        Self::COUNT
     }
-
-    fn create_regex_string() -> String {
-        let mut unit_options = String::with_capacity(Unit::iter().count() * 2); // Most Units are 1 characters long + 1 for the pipe
-        let mut iter = Unit::iter().peekable();
-        while let Some(value) = iter.next() {
-            unit_options.push_str(value.into());
-            if iter.peek().is_some() {
-                unit_options.push('|');
-            }
+    /// Every unit in declaration order. The same order as [Unit::iter]
+    pub const fn all() -> &'static [Unit] {
+        &[
+            Unit::Milliseconds,
+            Unit::Seconds,
+            Unit::Minutes,
+            Unit::Hours,
+            Unit::Days,
+        ]
+    }
+    /// The position of the unit in [Unit::all]. Such as `1 for Seconds`
+    ///
+    /// Not the enum discriminant
+    pub fn index(self) -> usize {
+        Self::all()
+            .iter()
+            .position(|unit| *unit == self)
+            .expect("Every unit is in Unit::all")
+    }
+    /// The unit at `index` in [Unit::all]. `None` if out of range
+    pub fn from_index(index: usize) -> Option<Unit> {
+        Self::all().get(index).copied()
+    }
+    /// The number of milliseconds in one of this unit
+    pub const fn milliseconds(&self) -> i64 {
+        match self {
+            Unit::Milliseconds => 1,
+            Unit::Seconds => SECOND,
+            Unit::Minutes => MINUTE,
+            Unit::Hours => HOUR,
+            Unit::Days => DAY,
+        }
+    }
+    /// What the unit is. Such as `Minute: 60 seconds`
+    pub fn description(&self) -> &'static str {
+        self.get_message().expect("Every unit has a strum message")
+    }
+    /// The name used when parsing and displaying. Such as `ms`
+    pub fn short_name(&self) -> &'static str {
+        self.into()
+    }
+    /// The singular long name of the unit. Such as `second`
+    pub const fn long_name(&self) -> &'static str {
+        match self {
+            Unit::Milliseconds => "millisecond",
+            Unit::Seconds => "second",
+            Unit::Minutes => "minute",
+            Unit::Hours => "hour",
+            Unit::Days => "day",
+        }
+    }
+    /// The plural long name of the unit. Such as `seconds`
+    pub const fn long_name_plural(&self) -> &'static str {
+        match self {
+            Unit::Milliseconds => "milliseconds",
+            Unit::Seconds => "seconds",
+            Unit::Minutes => "minutes",
+            Unit::Hours => "hours",
+            Unit::Days => "days",
         }
-        format!(r#"(?<length>[0-9]+)(?<unit>[{}]+)?"#, unit_options)
+    }
+    /// Finds the unit for a long name such as `secs` or `Minute`
+    ///
+    /// Case insensitive and either singular or plural. The reverse of [Unit::long_name] and [Unit::long_name_plural]
+    pub fn from_long_name(name: &str) -> Option<Unit> {
+        let name = name.to_ascii_lowercase();
+        let singular = name.strip_suffix('s').unwrap_or(&name);
+        match singular {
+            "msec" | "millisecond" => Some(Unit::Milliseconds),
+            "sec" | "second" => Some(Unit::Seconds),
+            "min" | "minute" => Some(Unit::Minutes),
+            "hr" | "hour" => Some(Unit::Hours),
+            "day" => Some(Unit::Days),
+            _ => None,
+        }
+    }
+    /// The next larger unit. Returns `None` for [Unit::Days]
+    pub fn next_larger(&self) -> Option<Unit> {
+        let mut units = Unit::iter();
+        units.find(|unit| unit == self)?;
+        units.next()
+    }
+    /// The next smaller unit. Returns `None` for [Unit::Milliseconds]
+    pub fn next_smaller(&self) -> Option<Unit> {
+        Unit::iter().take_while(|unit| unit != self).last()
+    }
+}
+impl ConfigUnit for Unit {
+    fn create_regex_string() -> String {
+        format!(
+            r#"^(?<length>-?[0-9]+)(?:\.(?<fraction>[0-9]+))?(?:(?<unit>{})|(?<invalid>.+))?$"#,
+            Self::unit_options()
+        )
     }
 }
 
@@ -108,7 +231,7 @@ impl Unit {
 /// duration_in_hours = "100h"
 /// duration_in_days = "10d"
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, From, Into, AsRef, Deref, DerefMut)]
+#[derive(Clone, Copy, From, Into, AsRef, Deref, DerefMut)]
 #[cfg_attr(feature = "digestible", derive(digestible::Digestible))]
 pub struct ConfigDuration {
     #[cfg_attr(feature = "digestible", digestible(digest_with = digest_with_hash))]
@@ -117,65 +240,505 @@ pub struct ConfigDuration {
     pub duration: Duration,
     pub unit: Unit,
 }
-serde_via_string_types!(ConfigDuration);
+serde_via_display!(ConfigDuration);
+/// Zero is always written as `0ms`. Whatever unit it is stored in
+///
+/// The alternate form `{:#}` uses the long unit name. Such as `1 hour` or `30 seconds`
 impl Display for ConfigDuration {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let length = match self.unit {
-            Unit::Milliseconds => self.duration.num_milliseconds(),
-            Unit::Seconds => self.duration.num_seconds(),
-            Unit::Minutes => self.duration.num_minutes(),
-            Unit::Hours => self.duration.num_hours(),
-            Unit::Days => self.duration.num_days(),
+        let (length, unit) = if self.is_zero() {
+            (0, Unit::Milliseconds)
+        } else {
+            self.as_parts()
         };
-
-        write!(f, "{}{}", length, self.unit)
+        if f.alternate() {
+            let name = if length.abs() == 1 {
+                unit.long_name()
+            } else {
+                unit.long_name_plural()
+            };
+            write!(f, "{} {}", length, name)
+        } else {
+            write!(f, "{}{}", length, unit)
+        }
+    }
+}
+/// The long form along with the exact length and the stored unit.
+/// Such as `ConfigDuration(1 second, 1500ms, Seconds)` for 1500ms stored in seconds
+///
+/// The long form alone is rounded to the unit. So it can hide the real value
+impl Debug for ConfigDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ConfigDuration")
+            .field(&format_args!("{:#}", self))
+            .field(&format_args!("{}ms", self.duration.num_milliseconds()))
+            .field(&self.unit)
+            .finish()
     }
 }
 impl FromStr for ConfigDuration {
     type Err = InvalidDurationError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_suffixed(s).map_err(|err| err.with_input(s))
+    }
+}
+impl ConfigDuration {
+    /// Parses a duration with a suffix. [FromStr] without the input attached to errors
+    fn parse_suffixed(s: &str) -> Result<Self, InvalidDurationError> {
         // TODO:  Support for more complex durations like "1h30m"
+        // A length can be negative and have a fraction. Such as -1.5h
+        if s.len() > MAX_INPUT_LEN {
+            return Err(InvalidDurationError::from("Input is too long").with_span(0..s.len()));
+        }
         let regex = UNITS_REGEX.get_or_init(Unit::build_regex);
-        let captures = regex
-            .captures(s)
-            .ok_or_else(|| InvalidDurationError::from("Unable to parse duration"))?;
-        let length = captures
-            .name("length")
-            .unwrap()
+        let captures = regex.captures(s).ok_or_else(|| {
+            InvalidDurationError::from("Unable to parse duration").with_span(0..s.len())
+        })?;
+        if let Some(invalid) = captures.name("invalid") {
+            return Err(InvalidDurationError::from("Unknown unit").with_span(invalid.range()));
+        }
+        let length = captures.name("length").unwrap();
+        let length_span = length.range();
+        let negative = length.as_str().starts_with('-');
+        let whole = length
             .as_str()
-            .parse::<usize>()
-            .map_err(|v| InvalidDurationError::from(("Invalid Size", v.into())))?;
+            .trim_start_matches('-')
+            .parse::<i128>()
+            .map_err(|v| {
+                InvalidDurationError::from(("Invalid Size", v.into()))
+                    .with_span(length_span.clone())
+            })?;
 
         let unit = captures
             .name("unit")
             .map(|unit| {
-                Unit::from_str(unit.as_str())
-                    .map_err(|v| InvalidDurationError::from(("Invalid Size", v.into())))
+                Unit::from_str(unit.as_str()).map_err(|v| {
+                    InvalidDurationError::from(("Invalid Size", v.into())).with_span(unit.range())
+                })
             })
             .transpose()?
             .unwrap_or_default();
 
-        let duration = match unit {
-            Unit::Milliseconds => Duration::milliseconds(length as i64),
-            Unit::Seconds => Duration::seconds(length as i64),
-            Unit::Minutes => Duration::minutes(length as i64),
-            Unit::Hours => Duration::hours(length as i64),
-            Unit::Days => Duration::days(length as i64),
+        let unit_milliseconds = unit.milliseconds() as i128;
+        let mut milliseconds = whole.checked_mul(unit_milliseconds);
+        if let Some(fraction) = captures.name("fraction") {
+            let digits = fraction.as_str().trim_end_matches('0');
+            if digits.len() > 30 {
+                return Err(InvalidDurationError::from("Too many decimal places")
+                    .with_span(fraction.range()));
+            }
+            let scale = 10i128.pow(digits.len() as u32);
+            let numerator = digits.parse::<i128>().unwrap_or(0) * unit_milliseconds;
+            if numerator % scale != 0 {
+                return Err(InvalidDurationError::from(
+                    "Duration can not be more precise than a millisecond",
+                )
+                .with_span(fraction.range()));
+            }
+            milliseconds = milliseconds.and_then(|ms| ms.checked_add(numerator / scale));
+        }
+        let duration = milliseconds
+            .map(|ms| if negative { -ms } else { ms })
+            .and_then(|ms| i64::try_from(ms).ok())
+            .and_then(Duration::try_milliseconds)
+            .ok_or_else(|| {
+                InvalidDurationError::from("Duration is out of range").with_span(0..s.len())
+            })?;
+        // A fractional length such as 1.5h is stored in the largest unit that can represent it. 90m
+        let unit = if captures.name("fraction").is_some() {
+            Unit::iter()
+                .rev()
+                .filter(|smaller| *smaller <= unit)
+                .find(|smaller| duration.num_milliseconds() % smaller.milliseconds() == 0)
+                .unwrap_or_default()
+        } else {
+            unit
         };
         Ok(Self { duration, unit })
     }
 }
 extend_string_from_and_to!(ConfigDuration, InvalidDurationError);
+impl Add for ConfigDuration {
+    type Output = ConfigDuration;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
+}
+impl Sub for ConfigDuration {
+    type Output = ConfigDuration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting durations")
+    }
+}
+impl Sum for ConfigDuration {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Add::add).unwrap_or(ConfigDuration::ZERO)
+    }
+}
+impl<'a> Sum<&'a ConfigDuration> for ConfigDuration {
+    fn sum<I: Iterator<Item = &'a ConfigDuration>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
 impl ConfigDuration {
+    /// A duration of zero milliseconds
+    pub const ZERO: ConfigDuration = ConfigDuration {
+        duration: Duration::zero(),
+        unit: Unit::Milliseconds,
+    };
+    /// The largest duration `chrono` can represent
+    pub const MAX: ConfigDuration = ConfigDuration {
+        duration: Duration::MAX,
+        unit: Unit::Milliseconds,
+    };
+    /// The smallest (most negative) duration `chrono` can represent
+    pub const MIN: ConfigDuration = ConfigDuration {
+        duration: Duration::MIN,
+        unit: Unit::Milliseconds,
+    };
+
+    /// Parses a duration. The same as [FromStr::from_str] without needing the trait in scope
+    pub fn parse(s: &str) -> Result<Self, InvalidDurationError> {
+        Self::from_str(s)
+    }
+    /// Parses a duration also accepting long and plural unit names. Such as `5mins`, `10 secs` or `3 days`
+    ///
+    /// The long names are case insensitive. A space is allowed before any unit. Such as `5 ms`.
+    /// Ambiguous spellings such as `M` or `mo` are still rejected. [Display] always writes the short unit
+    pub fn from_str_lenient(s: &str) -> Result<Self, InvalidDurationError> {
+        let digits = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '-' || c == '.'))
+            .unwrap_or(s.len());
+        let unit = s[digits..].trim_start();
+        if digits == 0 || unit.is_empty() {
+            return Self::from_str(s);
+        }
+        let long_unit = Unit::from_long_name(unit);
+        Self::parse_with_unit(s, digits, long_unit.map_or(unit, |unit| unit.short_name()))
+    }
+    /// Parses the length of `s` followed by `unit`. Used when the unit was written differently in `s`
+    ///
+    /// Error spans point into `s`. A span in the unit covers everything after the space following the length
+    fn parse_with_unit(s: &str, digits: usize, unit: &str) -> Result<Self, InvalidDurationError> {
+        let unit_start = s.len() - s[digits..].trim_start().len();
+        Self::parse_suffixed(&format!("{}{}", &s[..digits], unit)).map_err(|mut err| {
+            err.2 = err.2.map(|span| match span.start {
+                0 if span.end > digits => 0..s.len(),
+                start if start >= digits => unit_start..s.len(),
+                _ => span,
+            });
+            err.with_input(s)
+        })
+    }
+    /// Parses a duration like [FromStr] but errors if there is no unit
+    ///
+    /// [FromStr] reads `100` as `100ms`. Which is easy to mistake for seconds
+    pub fn from_str_require_unit(s: &str) -> Result<Self, InvalidDurationError> {
+        let duration = Self::from_str(s)?;
+        if s.ends_with(|c: char| c.is_ascii_digit()) {
+            return Err(
+                InvalidDurationError::from("A unit is required").with_span(s.len()..s.len())
+            );
+        }
+        Ok(duration)
+    }
+    /// Parses `s` and writes it back in the largest unit that represents it exactly. Such as `2h` for `120 mins`
+    ///
+    /// Accepts everything [ConfigDuration::from_str_lenient] does. The result is a fixed point. Normalizing it again gives the same string
+    pub fn normalize_string(s: &str) -> Result<String, InvalidDurationError> {
+        let duration = Self::from_str_lenient(s)?;
+        Ok(Self::in_largest_exact_unit(duration.duration).to_string())
+    }
+    /// Wraps the duration in the largest unit that represents it exactly. Anything under a millisecond is dropped
+    pub(crate) fn in_largest_exact_unit(duration: Duration) -> Self {
+        let milliseconds = duration.num_milliseconds();
+        let unit = Unit::iter()
+            .rev()
+            .find(|unit| milliseconds % unit.milliseconds() == 0)
+            .unwrap_or_default();
+        Self {
+            duration: Duration::milliseconds(milliseconds),
+            unit,
+        }
+    }
+    /// The time from `earlier` until now. Negative if `earlier` is in the future
+    ///
+    /// Uses the largest unit that represents the result exactly. Anything under a millisecond is dropped
+    pub fn since(earlier: DateTime<Utc>) -> Self {
+        Self::in_largest_exact_unit(Utc::now() - earlier)
+    }
+    /// The time from now until `later`. Negative if `later` is in the past
+    ///
+    /// Uses the largest unit that represents the result exactly. Anything under a millisecond is dropped
+    pub fn until(later: DateTime<Utc>) -> Self {
+        Self::in_largest_exact_unit(later - Utc::now())
+    }
+    /// Parses a clock style duration. `HH:MM:SS` or `MM:SS`. Such as `01:30:00` for 90 minutes
+    ///
+    /// Each part is a whole number and may go past its usual limit. So `00:90` is 90 seconds.
+    /// Separate from [FromStr] which only reads suffixed durations
+    pub fn parse_clock(s: &str) -> Result<Self, InvalidDurationError> {
+        let parts: Vec<&str> = s.split(':').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return Err(
+                InvalidDurationError::from("Expected HH:MM:SS or MM:SS").with_span(0..s.len())
+            );
+        }
+        let mut seconds: i64 = 0;
+        let mut start = 0;
+        for part in parts {
+            let span = start..start + part.len();
+            start = span.end + 1;
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(InvalidDurationError::from("Invalid clock part").with_span(span));
+            }
+            seconds = part
+                .parse::<i64>()
+                .ok()
+                .and_then(|value| seconds.checked_mul(60)?.checked_add(value))
+                .ok_or_else(|| {
+                    InvalidDurationError::from("Duration is out of range").with_span(span)
+                })?;
+        }
+        let duration = Duration::try_seconds(seconds)
+            .ok_or_else(|| InvalidDurationError::from("Duration is out of range"))?;
+        let unit = [Unit::Hours, Unit::Minutes]
+            .into_iter()
+            .find(|unit| seconds * SECOND % unit.milliseconds() == 0)
+            .unwrap_or(Unit::Seconds);
+        Ok(Self { duration, unit })
+    }
     pub fn into_inner(self) -> Duration {
         self.duration
     }
+    /// The duration in its stored unit along with the unit. Such as `(90, Unit::Minutes)` for `90m`
+    ///
+    /// The length is truncated towards zero if the duration is not a whole number of the unit
+    ///
+    /// This never overflows. chrono limits a duration to `i64::MAX` milliseconds in either direction
+    pub fn as_parts(&self) -> (i64, Unit) {
+        let length = match self.unit {
+            Unit::Milliseconds => self.duration.num_milliseconds(),
+            Unit::Seconds => self.duration.num_seconds(),
+            Unit::Minutes => self.duration.num_minutes(),
+            Unit::Hours => self.duration.num_hours(),
+            Unit::Days => self.duration.num_days(),
+        };
+        (length, self.unit)
+    }
+    /// Formats the duration for people. Such as `1 day 6 hours` or `500 milliseconds`
+    ///
+    /// Parts that are zero are left out. Negative durations are prefixed with `minus`.
+    /// Anything under a millisecond is dropped
+    pub fn human_readable(&self) -> String {
+        let decomposed = self.decompose();
+        if decomposed.is_empty() {
+            return "0 milliseconds".to_owned();
+        }
+        let mut parts = Vec::with_capacity(decomposed.len() + 1);
+        if self.duration < Duration::zero() {
+            parts.push("minus".to_owned());
+        }
+        for (unit, count) in decomposed {
+            match count.abs() {
+                1 => parts.push(format!("1 {}", unit.long_name())),
+                count => parts.push(format!("{} {}", count, unit.long_name_plural())),
+            }
+        }
+        parts.join(" ")
+    }
+    /// Formats the duration in `unit`. Such as `120m` for `2h` in [Unit::Minutes]
+    ///
+    /// A fraction is written when it does not divide evenly. Such as `1.5s` for `1500ms` in [Unit::Seconds]
+    pub fn display_in(&self, unit: Unit) -> String {
+        let milliseconds = self.duration.num_milliseconds();
+        if milliseconds % unit.milliseconds() == 0 {
+            format!("{}{}", milliseconds / unit.milliseconds(), unit)
+        } else {
+            format!(
+                "{}{}",
+                milliseconds as f64 / unit.milliseconds() as f64,
+                unit
+            )
+        }
+    }
+    /// Breaks the duration into the largest units. Such as `[(Hours, 1), (Minutes, 30)]` for `90m`
+    ///
+    /// Units with a count of zero are left out. So zero is an empty `Vec`.
+    /// The counts of a negative duration are all negative. Anything under a millisecond is dropped
+    pub fn decompose(&self) -> Vec<(Unit, i64)> {
+        let sign = self.duration.num_milliseconds().signum();
+        let mut remaining = self.duration.num_milliseconds().abs();
+        let mut parts = Vec::new();
+        for unit in Unit::iter().rev() {
+            let count = remaining / unit.milliseconds();
+            if count > 0 {
+                parts.push((unit, count * sign));
+                remaining %= unit.milliseconds();
+            }
+        }
+        parts
+    }
+    /// Formats the duration as compact segments. Such as `1w2d3h`
+    ///
+    /// Segments that are zero are left out. Unlike [Display] this is only meant for people to read
+    pub fn to_humanized(&self) -> String {
+        const SUFFIXES: [&str; 6] = ["w", "d", "h", "m", "s", "ms"];
+        let counts = self.split_into([WEEK, DAY, HOUR, MINUTE, SECOND, 1]);
+        if counts.iter().all(|count| *count == 0) {
+            return "0ms".to_owned();
+        }
+        let mut humanized = String::new();
+        if self.duration < Duration::zero() {
+            humanized.push('-');
+        }
+        for (count, suffix) in counts.into_iter().zip(SUFFIXES) {
+            if count != 0 {
+                humanized.push_str(&format!("{}{}", count, suffix));
+            }
+        }
+        humanized
+    }
+    /// Splits the absolute number of milliseconds into counts of each length. Largest first
+    fn split_into<const N: usize>(&self, lengths: [i64; N]) -> [i64; N] {
+        let mut remaining = self.duration.num_milliseconds().abs();
+        lengths.map(|length| {
+            let count = remaining / length;
+            remaining %= length;
+            count
+        })
+    }
+    /// Returns the duration as a whole number of seconds
+    ///
+    /// Errors if the duration has a sub-second part. Such as `1500ms`
+    pub fn as_whole_seconds_exact(&self) -> Result<i64, InvalidDurationError> {
+        if self.duration.subsec_nanos() != 0 {
+            return Err(InvalidDurationError::from(
+                "Duration is not a whole number of seconds",
+            ));
+        }
+        Ok(self.duration.num_seconds())
+    }
+    /// Creates a duration from a floating point number of seconds. Such as `1.5`
+    ///
+    /// The value is rounded to the nearest millisecond.
+    /// Errors if the value is NaN, infinite or out of range
+    pub fn from_secs_f64(secs: f64) -> Result<ConfigDuration, InvalidDurationError> {
+        if !secs.is_finite() {
+            return Err(InvalidDurationError::from(
+                "Duration must be a finite number",
+            ));
+        }
+        let milliseconds = (secs * 1000.0).round();
+        // i64::MAX as f64 rounds up to 2^63. So it is out of range itself
+        if milliseconds.abs() >= i64::MAX as f64 {
+            return Err(InvalidDurationError::from("Duration is out of range"));
+        }
+        let milliseconds = milliseconds as i64;
+        let duration = Duration::try_milliseconds(milliseconds)
+            .ok_or(InvalidDurationError::from("Duration is out of range"))?;
+        let unit = if milliseconds % SECOND == 0 {
+            Unit::Seconds
+        } else {
+            Unit::Milliseconds
+        };
+        Ok(ConfigDuration { duration, unit })
+    }
+    /// If the duration is zero. In any unit
+    pub fn is_zero(&self) -> bool {
+        self.duration.is_zero()
+    }
+    /// `numerator / denominator` as a float. Such as `0.25` for `15m` of `1h`
+    ///
+    /// Follows float division. A zero denominator gives infinity with the sign of the numerator, or `NaN` if the numerator is also zero.
+    /// Negative durations give negative ratios
+    pub fn ratio(numerator: ConfigDuration, denominator: ConfigDuration) -> f64 {
+        f64::from(numerator) / f64::from(denominator)
+    }
+    /// How much of `total` this duration is as a percentage. Such as `25.0` for `15m` of `1h`
+    ///
+    /// A zero `total` gives `0.0`. Unlike [ConfigDuration::ratio] which follows float division
+    pub fn percent_of(&self, total: &ConfigDuration) -> f64 {
+        if total.is_zero() {
+            return 0.0;
+        }
+        Self::ratio(*self, *total) * 100.0
+    }
+    /// Restricts the duration to the range `min..=max`. Comparing the full durations so the units can differ
+    ///
+    /// The result keeps the unit of whichever value is returned. `self` if it is in range, otherwise the bound
+    ///
+    /// # Panics
+    /// If `min > max`. The same as [Ord::clamp]
+    pub fn clamp(self, min: ConfigDuration, max: ConfigDuration) -> ConfigDuration {
+        Ord::clamp(self, min, max)
+    }
+    /// The longest duration in the slice. `None` if it is empty
+    ///
+    /// Equal durations in different units such as `1m` and `60s` follow [Iterator::max]. The last one is returned
+    pub fn max_of(durations: &[ConfigDuration]) -> Option<ConfigDuration> {
+        durations.iter().copied().max()
+    }
+    /// The shortest duration in the slice. `None` if it is empty
+    ///
+    /// Equal durations in different units follow [Iterator::min]. The first one is returned
+    pub fn min_of(durations: &[ConfigDuration]) -> Option<ConfigDuration> {
+        durations.iter().copied().min()
+    }
+    /// Adds the two durations. Returning `None` if the result is out of range
+    ///
+    /// The result uses the smaller unit of the two
+    pub fn checked_add(self, rhs: ConfigDuration) -> Option<ConfigDuration> {
+        self.duration
+            .checked_add(&rhs.duration)
+            .map(|duration| ConfigDuration {
+                duration,
+                unit: self.unit.min(rhs.unit),
+            })
+    }
+    /// Subtracts `rhs` from this duration. Returning `None` if the result is out of range
+    ///
+    /// The result uses the smaller unit of the two
+    pub fn checked_sub(self, rhs: ConfigDuration) -> Option<ConfigDuration> {
+        self.duration
+            .checked_sub(&rhs.duration)
+            .map(|duration| ConfigDuration {
+                duration,
+                unit: self.unit.min(rhs.unit),
+            })
+    }
+}
+/// Compares the durations. So `3600s` equals `1h`
+impl PartialEq for ConfigDuration {
+    fn eq(&self, other: &Self) -> bool {
+        self.duration == other.duration
+    }
+}
+impl Eq for ConfigDuration {}
+/// Parses the string and compares the durations. `false` if it does not parse
+impl PartialEq<str> for ConfigDuration {
+    fn eq(&self, other: &str) -> bool {
+        ConfigDuration::from_str(other).is_ok_and(|other| *self == other)
+    }
+}
+impl PartialEq<&str> for ConfigDuration {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+impl std::hash::Hash for ConfigDuration {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.duration.hash(state);
+    }
 }
-#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for ConfigDuration {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.duration.partial_cmp(&other.duration)
+        Some(self.cmp(other))
     }
 }
 impl Ord for ConfigDuration {
@@ -197,3 +760,690 @@ impl From<ConfigDuration> for Duration {
         duration.duration
     }
 }
+/// The number of seconds with the fractional part
+impl From<ConfigDuration> for f64 {
+    fn from(duration: ConfigDuration) -> Self {
+        duration.num_seconds() as f64 + duration.subsec_nanos() as f64 / 1_000_000_000.0
+    }
+}
+
+/// The smallest duration. `None` if the iterator is empty. The same as [Iterator::min]
+pub fn min_duration<I: IntoIterator<Item = ConfigDuration>>(
+    durations: I,
+) -> Option<ConfigDuration> {
+    durations.into_iter().min()
+}
+/// The largest duration. `None` if the iterator is empty. The same as [Iterator::max]
+pub fn max_duration<I: IntoIterator<Item = ConfigDuration>>(
+    durations: I,
+) -> Option<ConfigDuration> {
+    durations.into_iter().max()
+}
+/// The smallest and largest duration in one pass. `None` if the iterator is empty
+///
+/// A single duration is both the min and the max
+pub fn min_max_duration<I: IntoIterator<Item = ConfigDuration>>(
+    durations: I,
+) -> Option<(ConfigDuration, ConfigDuration)> {
+    durations
+        .into_iter()
+        .fold(None, |min_max, duration| match min_max {
+            None => Some((duration, duration)),
+            Some((min, max)) => Some((min.min(duration), max.max(duration))),
+        })
+}
+/// For `#[serde(deserialize_with = "...")]` on an `Option<ConfigDuration>`. An empty string is `None`
+///
+/// Add `#[serde(default)]` so a missing field is also `None`
+pub fn deserialize_optional_duration<'de, D>(
+    deserializer: D,
+) -> Result<Option<ConfigDuration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::macros::deserialize_optional_from_str(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    pub fn test_constants() {
+        assert!(ConfigDuration::ZERO.is_zero());
+        assert!(ConfigDuration::MIN < ConfigDuration::ZERO);
+        for unit in Unit::iter() {
+            let number = rand::thread_rng().gen_range(1..10000);
+            let duration = ConfigDuration::from_str(&format!("{}{}", number, unit)).unwrap();
+            assert!(ConfigDuration::ZERO < duration);
+            assert!(duration < ConfigDuration::MAX);
+            assert!(ConfigDuration::MIN < duration);
+        }
+    }
+    #[test]
+    pub fn test_arithmetic() {
+        let sum =
+            ConfigDuration::from_str("1h").unwrap() + ConfigDuration::from_str("30m").unwrap();
+        assert_eq!(sum.to_string(), "90m");
+        let difference =
+            ConfigDuration::from_str("1h").unwrap() - ConfigDuration::from_str("30m").unwrap();
+        assert_eq!(difference.to_string(), "30m");
+
+        assert!(ConfigDuration::MAX
+            .checked_add(ConfigDuration::from_str("1ms").unwrap())
+            .is_none());
+        assert!(ConfigDuration::MIN
+            .checked_sub(ConfigDuration::from_str("1ms").unwrap())
+            .is_none());
+    }
+    #[test]
+    pub fn test_as_whole_seconds_exact() {
+        let duration = ConfigDuration::from_str("5m").unwrap();
+        assert_eq!(duration.as_whole_seconds_exact().unwrap(), 300);
+        let duration = ConfigDuration::from_str("2000ms").unwrap();
+        assert_eq!(duration.as_whole_seconds_exact().unwrap(), 2);
+        let duration = ConfigDuration::from_str("1500ms").unwrap();
+        assert!(duration.as_whole_seconds_exact().is_err());
+    }
+    #[test]
+    pub fn test_as_parts() {
+        let duration = ConfigDuration::from_str("90m").unwrap();
+        assert_eq!(duration.as_parts(), (90, Unit::Minutes));
+        let duration = ConfigDuration::from_str("100").unwrap();
+        assert_eq!(duration.as_parts(), (100, Unit::Milliseconds));
+    }
+    #[test]
+    pub fn test_sum() {
+        let durations = ["1h", "30m", "30m"].map(|v| ConfigDuration::from_str(v).unwrap());
+        let total: ConfigDuration = durations.iter().sum();
+        assert_eq!(total.to_string(), "120m");
+        assert_eq!(total.duration, Duration::hours(2));
+
+        let empty: ConfigDuration = Vec::<ConfigDuration>::new().into_iter().sum();
+        assert_eq!(empty, ConfigDuration::ZERO);
+    }
+    #[test]
+    pub fn test_error_span() {
+        let input = "100XB";
+        let error = ConfigDuration::from_str(input).unwrap_err();
+        let span = error.span().unwrap();
+        assert_eq!(span, 3..5);
+        assert_eq!(&input[span], "XB");
+
+        let error = ConfigDuration::from_str("ms").unwrap_err();
+        assert_eq!(error.span(), Some(0..2));
+    }
+    #[test]
+    pub fn test_human_readable() {
+        let human_readable = |duration: Duration| ConfigDuration::from(duration).human_readable();
+        assert_eq!(
+            human_readable(Duration::days(1) + Duration::hours(6)),
+            "1 day 6 hours"
+        );
+        assert_eq!(
+            human_readable(Duration::minutes(30) + Duration::seconds(15)),
+            "30 minutes 15 seconds"
+        );
+        assert_eq!(
+            human_readable(Duration::milliseconds(500)),
+            "500 milliseconds"
+        );
+        assert_eq!(human_readable(Duration::hours(1)), "1 hour");
+        assert_eq!(
+            human_readable(Duration::seconds(1) + Duration::milliseconds(1)),
+            "1 second 1 millisecond"
+        );
+        assert_eq!(
+            human_readable(Duration::microseconds(999)),
+            "0 milliseconds"
+        );
+        assert_eq!(human_readable(Duration::zero()), "0 milliseconds");
+        assert_eq!(
+            human_readable(-(Duration::hours(2) + Duration::minutes(30))),
+            "minus 2 hours 30 minutes"
+        );
+    }
+    #[test]
+    pub fn test_to_humanized() {
+        let humanized = |duration: Duration| ConfigDuration::from(duration).to_humanized();
+        assert_eq!(
+            humanized(Duration::weeks(1) + Duration::days(2) + Duration::hours(3)),
+            "1w2d3h"
+        );
+        assert_eq!(humanized(Duration::hours(1)), "1h");
+        assert_eq!(humanized(Duration::weeks(2)), "2w");
+        assert_eq!(
+            humanized(Duration::minutes(90) + Duration::milliseconds(5)),
+            "1h30m5ms"
+        );
+        assert_eq!(humanized(Duration::zero()), "0ms");
+        assert_eq!(humanized(-Duration::seconds(61)), "-1m1s");
+    }
+    #[test]
+    pub fn test_serialize_matches_display() {
+        for unit in Unit::iter() {
+            let duration = ConfigDuration::from_str(&format!("30{}", unit)).unwrap();
+            assert_eq!(
+                serde_json::to_string(&duration).unwrap(),
+                serde_json::to_string(&duration.to_string()).unwrap()
+            );
+        }
+    }
+    #[test]
+    pub fn test_unit_navigation() {
+        assert_eq!(Unit::Seconds.next_larger(), Some(Unit::Minutes));
+        assert_eq!(Unit::Seconds.next_smaller(), Some(Unit::Milliseconds));
+        assert_eq!(Unit::Days.next_larger(), None);
+        assert_eq!(Unit::Milliseconds.next_smaller(), None);
+    }
+    #[test]
+    pub fn test_from_secs_f64() {
+        let duration = ConfigDuration::from_secs_f64(1.5).unwrap();
+        assert_eq!(duration.num_milliseconds(), 1500);
+        assert_eq!(duration.to_string(), "1500ms");
+        assert_eq!(
+            ConfigDuration::from_secs_f64(30.0).unwrap().to_string(),
+            "30s"
+        );
+        assert!(ConfigDuration::from_secs_f64(0.0004).unwrap().is_zero());
+        assert_eq!(
+            ConfigDuration::from_secs_f64(-2.0).unwrap().num_seconds(),
+            -2
+        );
+        // One million years
+        let large = ConfigDuration::from_secs_f64(3.1536e13).unwrap();
+        assert_eq!(large.num_days(), 365_000_000);
+
+        assert!(ConfigDuration::from_secs_f64(f64::NAN).is_err());
+        assert!(ConfigDuration::from_secs_f64(f64::INFINITY).is_err());
+        assert!(ConfigDuration::from_secs_f64(f64::NEG_INFINITY).is_err());
+        assert!(ConfigDuration::from_secs_f64(1e300).is_err());
+    }
+    #[test]
+    pub fn test_display_large_durations() {
+        let days = ConfigDuration {
+            duration: Duration::days(5000),
+            unit: Unit::Milliseconds,
+        };
+        assert_eq!(days.to_string(), "432000000000ms");
+        assert_eq!(ConfigDuration::from_str(&days.to_string()).unwrap(), days);
+
+        assert_eq!(ConfigDuration::MAX.to_string(), format!("{}ms", i64::MAX));
+        assert_eq!(ConfigDuration::MIN.to_string(), format!("{}ms", -i64::MAX));
+        let max_days = ConfigDuration {
+            duration: Duration::MAX,
+            unit: Unit::Days,
+        };
+        assert_eq!(max_days.to_string(), format!("{}d", i64::MAX / DAY));
+    }
+    #[test]
+    pub fn test_clamp() {
+        let min = ConfigDuration::from_str("1s").unwrap();
+        let max = ConfigDuration::from_str("1m").unwrap();
+        let inside = ConfigDuration::from_str("30s").unwrap();
+        assert_eq!(inside.clamp(min, max), inside);
+        assert_eq!(ConfigDuration::ZERO.clamp(min, max), min);
+        assert_eq!(ConfigDuration::from_str("1h").unwrap().clamp(min, max), max);
+        assert_eq!(min.clamp(min, max), min);
+        assert_eq!(max.clamp(min, max), max);
+    }
+    #[test]
+    pub fn test_from_str_require_unit() {
+        let error = ConfigDuration::from_str_require_unit("100").unwrap_err();
+        assert_eq!(error.span(), Some(3..3));
+        assert_eq!(
+            ConfigDuration::from_str_require_unit("100s").unwrap(),
+            ConfigDuration::from_str("100s").unwrap()
+        );
+        assert!(ConfigDuration::from_str_require_unit("100ms").is_ok());
+        assert!(ConfigDuration::from_str_require_unit("100x").is_err());
+        // The lenient parser is unchanged
+        assert_eq!(
+            ConfigDuration::from_str("100").unwrap().num_milliseconds(),
+            100
+        );
+    }
+    #[test]
+    pub fn test_into_f64() {
+        assert_eq!(f64::from(ConfigDuration::from_str("1500ms").unwrap()), 1.5);
+        assert_eq!(f64::from(ConfigDuration::from_str("2m").unwrap()), 120.0);
+        assert_eq!(
+            f64::from(ConfigDuration::from(Duration::milliseconds(-1500))),
+            -1.5
+        );
+    }
+    #[test]
+    pub fn test_clamp_mixed_units() {
+        let hour = ConfigDuration::from_str("1h").unwrap();
+        let min = ConfigDuration::from_str("59m").unwrap();
+        let max = ConfigDuration::from_str("7200s").unwrap();
+        let clamped = hour.clamp(min, max);
+        assert_eq!(clamped, hour);
+        assert_eq!(clamped.as_parts(), (1, Unit::Hours));
+
+        let below = ConfigDuration::from_str("30m").unwrap().clamp(hour, max);
+        assert_eq!(below.as_parts(), (1, Unit::Hours));
+        let above = ConfigDuration::from_str("3h").unwrap().clamp(min, max);
+        assert_eq!(above.as_parts(), (7200, Unit::Seconds));
+    }
+    #[test]
+    pub fn test_from_str_lenient() {
+        for (input, expected) in [
+            ("5mins", "5m"),
+            ("5 min", "5m"),
+            ("10secs", "10s"),
+            ("10 Seconds", "10s"),
+            ("3days", "3d"),
+            ("1 day", "1d"),
+            ("2hrs", "2h"),
+            ("2 hours", "2h"),
+            ("250 milliseconds", "250ms"),
+            ("250msecs", "250ms"),
+            ("30s", "30s"),
+            ("5 ms", "5ms"),
+            ("-1.5 h", "-90m"),
+            ("30\ts", "30s"),
+        ] {
+            let duration = ConfigDuration::from_str_lenient(input).unwrap();
+            assert_eq!(duration.to_string(), expected, "Input: {:?}", input);
+        }
+        // Ambiguous or unknown spellings
+        for input in [
+            "5M", "5 M", "5mo", "5 months", "5mi", "5 weeks", "mins", "5 ",
+        ] {
+            assert!(
+                ConfigDuration::from_str_lenient(input).is_err(),
+                "Input: {:?}",
+                input
+            );
+        }
+        assert!(ConfigDuration::from_str("5mins").is_err());
+        assert!(ConfigDuration::from_str("5 ms").is_err());
+        let error = ConfigDuration::from_str_lenient("5 xs").unwrap_err();
+        assert_eq!(error.input(), Some("5 xs"));
+        assert_eq!(error.span(), Some(2..4));
+    }
+    #[test]
+    pub fn test_decompose() {
+        assert_eq!(
+            ConfigDuration::from_str("90m").unwrap().decompose(),
+            vec![(Unit::Hours, 1), (Unit::Minutes, 30)]
+        );
+        assert_eq!(
+            ConfigDuration::from(Duration::milliseconds(-90_500)).decompose(),
+            vec![
+                (Unit::Minutes, -1),
+                (Unit::Seconds, -30),
+                (Unit::Milliseconds, -500)
+            ]
+        );
+        assert!(ConfigDuration::ZERO.decompose().is_empty());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let milliseconds = rng.gen_range(-(DAY * 10_000)..DAY * 10_000);
+            let duration = ConfigDuration::from(Duration::milliseconds(milliseconds));
+            let total: i64 = duration
+                .decompose()
+                .into_iter()
+                .map(|(unit, count)| unit.milliseconds() * count)
+                .sum();
+            assert_eq!(total, milliseconds);
+        }
+    }
+    #[test]
+    pub fn test_parse_signed_fraction() {
+        let duration = ConfigDuration::from_str("-1.5h").unwrap();
+        assert_eq!(duration.num_minutes(), -90);
+        assert_eq!(duration.as_parts(), (-90, Unit::Minutes));
+        assert_eq!(duration.to_string(), "-90m");
+
+        let duration = ConfigDuration::from_str("1.25d").unwrap();
+        assert_eq!(duration.num_hours(), 30);
+        assert_eq!(duration.to_string(), "30h");
+        assert_eq!(
+            ConfigDuration::from_str("1.5s").unwrap().to_string(),
+            "1500ms"
+        );
+        assert_eq!(ConfigDuration::from_str("2.0h").unwrap().to_string(), "2h");
+        assert_eq!(ConfigDuration::from_str("-30s").unwrap().num_seconds(), -30);
+
+        // Display round trips. Even for fractions which are written in the smaller unit
+        for input in ["-1.5h", "1.25d", "0.001s", "-7d", "-5"] {
+            let duration = ConfigDuration::from_str(input).unwrap();
+            assert_eq!(
+                ConfigDuration::from_str(&duration.to_string()).unwrap(),
+                duration
+            );
+        }
+        // Lossy. Less than a millisecond
+        let error = ConfigDuration::from_str("1.0005s").unwrap_err();
+        assert_eq!(error.span(), Some(2..6));
+        assert!(ConfigDuration::from_str("1.5ms").is_err());
+        assert!(ConfigDuration::from_str("1.h").is_err());
+        assert!(ConfigDuration::from_str(".5h").is_err());
+        assert!(ConfigDuration::from_str("--1h").is_err());
+        assert!(ConfigDuration::from_str("999999999999999999d").is_err());
+    }
+    #[test]
+    pub fn test_eq_across_units() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        fn hash(duration: &ConfigDuration) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            duration.hash(&mut hasher);
+            hasher.finish()
+        }
+        let seconds = ConfigDuration::from_str("3600s").unwrap();
+        let hour = ConfigDuration::from_str("1h").unwrap();
+        assert_eq!(seconds, hour);
+        assert_eq!(seconds.cmp(&hour), Ordering::Equal);
+        assert_eq!(hash(&seconds), hash(&hour));
+        assert_ne!(seconds, ConfigDuration::from_str("3599s").unwrap());
+        // The unit is still kept
+        assert_eq!(seconds.as_parts(), (3600, Unit::Seconds));
+    }
+    #[test]
+    pub fn test_requires_digits() {
+        for input in ["ms", "s", "", "ms5", "5ms5", " 5ms", "5ms ", "-ms"] {
+            let error = ConfigDuration::from_str(input).unwrap_err();
+            assert!(error.span().is_some(), "Input: {:?}", input);
+        }
+        let error = ConfigDuration::from_str("ms").unwrap_err();
+        assert_eq!(error.span(), Some(0..2));
+        assert!(error.to_string().starts_with("Unable to parse duration"));
+    }
+    #[test]
+    pub fn test_ratio() {
+        let elapsed = ConfigDuration::from_str("15m").unwrap();
+        let total = ConfigDuration::from_str("1h").unwrap();
+        assert_eq!(ConfigDuration::ratio(elapsed, total), 0.25);
+        assert_eq!(elapsed.percent_of(&total), 25.0);
+        assert_eq!(
+            ConfigDuration::from_str("1500ms")
+                .unwrap()
+                .percent_of(&ConfigDuration::from_str("1s").unwrap()),
+            150.0
+        );
+
+        let negative = ConfigDuration::from_str("-15m").unwrap();
+        assert_eq!(negative.percent_of(&total), -25.0);
+        let negative_total = ConfigDuration::from_str("-1h").unwrap();
+        assert_eq!(ConfigDuration::ratio(negative, negative_total), 0.25);
+
+        assert_eq!(
+            ConfigDuration::ratio(elapsed, ConfigDuration::ZERO),
+            f64::INFINITY
+        );
+        assert_eq!(
+            ConfigDuration::ratio(negative, ConfigDuration::ZERO),
+            f64::NEG_INFINITY
+        );
+        assert!(ConfigDuration::ratio(ConfigDuration::ZERO, ConfigDuration::ZERO).is_nan());
+    }
+    #[test]
+    pub fn test_zero_display() {
+        for input in ["0ms", "0s", "0m", "0h", "0d", "-0d", "0.0h"] {
+            let duration = ConfigDuration::from_str(input).unwrap();
+            assert!(duration.is_zero());
+            assert_eq!(duration.to_string(), "0ms");
+        }
+        assert!(!ConfigDuration::from_str("1ms").unwrap().is_zero());
+    }
+    #[test]
+    pub fn test_deserialize_optional_duration() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(default, deserialize_with = "deserialize_optional_duration")]
+            timeout: Option<ConfigDuration>,
+        }
+        let parse = |json: &str| serde_json::from_str::<Config>(json).map(|c| c.timeout);
+        assert_eq!(parse(r#"{"timeout": ""}"#).unwrap(), None);
+        assert_eq!(parse(r#"{}"#).unwrap(), None);
+        assert_eq!(
+            parse(r#"{"timeout": "30s"}"#).unwrap(),
+            Some(ConfigDuration::from_str("30s").unwrap())
+        );
+        assert!(parse(r#"{"timeout": "30x"}"#).is_err());
+    }
+    #[test]
+    pub fn test_max_of_min_of() {
+        let durations = [
+            ConfigDuration::from_str("90s").unwrap(),
+            ConfigDuration::from_str("1h").unwrap(),
+            ConfigDuration::from_str("500ms").unwrap(),
+            ConfigDuration::from_str("2m").unwrap(),
+        ];
+        assert_eq!(
+            ConfigDuration::max_of(&durations).unwrap().to_string(),
+            "1h"
+        );
+        assert_eq!(
+            ConfigDuration::min_of(&durations).unwrap().to_string(),
+            "500ms"
+        );
+        assert_eq!(ConfigDuration::max_of(&[]), None);
+        assert_eq!(ConfigDuration::min_of(&[]), None);
+    }
+    #[test]
+    pub fn test_alternate_format() {
+        let duration = ConfigDuration::from_str("30s").unwrap();
+        assert_eq!(format!("{}", duration), "30s");
+        assert_eq!(format!("{:#}", duration), "30 seconds");
+        assert_eq!(
+            format!("{:#}", ConfigDuration::from_str("1h").unwrap()),
+            "1 hour"
+        );
+        assert_eq!(
+            format!("{:#}", ConfigDuration::from_str("5d").unwrap()),
+            "5 days"
+        );
+        assert_eq!(
+            format!("{:#}", ConfigDuration::from_str("-1m").unwrap()),
+            "-1 minute"
+        );
+        assert_eq!(
+            format!("{:#}", ConfigDuration::from_str("0d").unwrap()),
+            "0 milliseconds"
+        );
+        assert_eq!(
+            format!("{:?}", duration),
+            "ConfigDuration(30 seconds, 30000ms, Seconds)"
+        );
+        let inexact = ConfigDuration {
+            duration: Duration::milliseconds(1500),
+            unit: Unit::Seconds,
+        };
+        assert_eq!(
+            format!("{:?}", inexact),
+            "ConfigDuration(1 second, 1500ms, Seconds)"
+        );
+    }
+    #[test]
+    pub fn test_display_in() {
+        let duration = ConfigDuration::from_str("2h").unwrap();
+        assert_eq!(duration.display_in(Unit::Minutes), "120m");
+        assert_eq!(duration.display_in(Unit::Days), "0.08333333333333333d");
+        let duration = ConfigDuration::from_str("1500ms").unwrap();
+        assert_eq!(duration.display_in(Unit::Seconds), "1.5s");
+        assert_eq!(duration.display_in(Unit::Milliseconds), "1500ms");
+        assert_eq!(
+            ConfigDuration::from_str(&duration.display_in(Unit::Seconds)).unwrap(),
+            duration
+        );
+        assert_eq!(
+            ConfigDuration::from_str("-90s")
+                .unwrap()
+                .display_in(Unit::Minutes),
+            "-1.5m"
+        );
+    }
+    #[test]
+    pub fn test_min_max_duration() {
+        assert_eq!(min_duration(Vec::<ConfigDuration>::new()), None);
+        assert_eq!(max_duration(Vec::<ConfigDuration>::new()), None);
+        assert_eq!(min_max_duration(Vec::<ConfigDuration>::new()), None);
+
+        let one = ConfigDuration::from_str("1s").unwrap();
+        assert_eq!(min_max_duration([one]), Some((one, one)));
+
+        let durations = ["90s", "1h", "500ms", "2m"].map(|d| ConfigDuration::from_str(d).unwrap());
+        assert_eq!(min_duration(durations).unwrap().to_string(), "500ms");
+        assert_eq!(max_duration(durations).unwrap().to_string(), "1h");
+        let (min, max) = min_max_duration(durations).unwrap();
+        assert_eq!(
+            (min.to_string(), max.to_string()),
+            ("500ms".to_owned(), "1h".to_owned())
+        );
+    }
+    #[test]
+    pub fn test_parse_clock() {
+        let duration = ConfigDuration::parse_clock("01:30:00").unwrap();
+        assert_eq!(duration.num_minutes(), 90);
+        assert_eq!(duration.to_string(), "90m");
+        let duration = ConfigDuration::parse_clock("00:90").unwrap();
+        assert_eq!(duration.num_seconds(), 90);
+        assert_eq!(duration.to_string(), "90s");
+        assert_eq!(
+            ConfigDuration::parse_clock("02:00:00").unwrap().to_string(),
+            "2h"
+        );
+        assert_eq!(
+            ConfigDuration::parse_clock("00:00").unwrap(),
+            ConfigDuration::ZERO
+        );
+
+        for invalid in [
+            "", "90", "1:2:3:4", "01::00", "-1:00", "1.5:00", "aa:bb", "01:30s",
+        ] {
+            assert!(
+                ConfigDuration::parse_clock(invalid).is_err(),
+                "Input: {:?}",
+                invalid
+            );
+        }
+        assert_eq!(
+            ConfigDuration::parse_clock("01:x0:00").unwrap_err().span(),
+            Some(3..5)
+        );
+        assert!(ConfigDuration::parse_clock("99999999999999999:00:00").is_err());
+        assert!(ConfigDuration::from_str("01:30:00").is_err());
+    }
+    #[test]
+    pub fn test_unit_names() {
+        assert_eq!(Unit::Minutes.description(), "Minute: 60 seconds");
+        assert_eq!(Unit::Minutes.short_name(), "m");
+        assert_eq!(Unit::Minutes.long_name(), "minute");
+        assert_eq!(Unit::Minutes.long_name_plural(), "minutes");
+        for unit in Unit::iter() {
+            assert!(!unit.description().is_empty());
+            assert_eq!(Unit::from_str(unit.short_name()).unwrap(), unit);
+            assert_eq!(Unit::from_long_name(unit.long_name()), Some(unit));
+            assert_eq!(Unit::from_long_name(unit.long_name_plural()), Some(unit));
+        }
+    }
+    #[test]
+    pub fn test_normalize_string() {
+        assert_eq!(ConfigDuration::normalize_string("120 mins").unwrap(), "2h");
+        assert_eq!(ConfigDuration::normalize_string("90000ms").unwrap(), "90s");
+        assert_eq!(ConfigDuration::normalize_string("1.5h").unwrap(), "90m");
+        assert_eq!(ConfigDuration::normalize_string("48h").unwrap(), "2d");
+        assert_eq!(ConfigDuration::normalize_string("-60s").unwrap(), "-1m");
+        assert_eq!(ConfigDuration::normalize_string("0d").unwrap(), "0ms");
+        assert!(ConfigDuration::normalize_string("1y").is_err());
+        for input in ["120 mins", "1500ms", "36h", "0s", "-90s"] {
+            let once = ConfigDuration::normalize_string(input).unwrap();
+            assert_eq!(ConfigDuration::normalize_string(&once).unwrap(), once);
+        }
+    }
+    #[test]
+    pub fn test_long_input() {
+        let start = std::time::Instant::now();
+        let input = "1".repeat(1024 * 1024);
+        let error = ConfigDuration::from_str(&input).unwrap_err();
+        assert_eq!(error.span(), Some(0..input.len()));
+        assert!(ConfigDuration::from_str(&format!("1.{}s", "5".repeat(1024 * 1024))).is_err());
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+        let input = format!("-{}.{}ms", i64::MAX, "0".repeat(30));
+        assert!(input.len() <= MAX_INPUT_LEN);
+        assert!(ConfigDuration::from_str(&input).is_ok());
+    }
+    #[test]
+    pub fn test_eq_str() {
+        let duration = ConfigDuration::from_str("1h").unwrap();
+        assert_eq!(duration, "1h");
+        assert_eq!(duration, "60m");
+        assert!(duration == *"3600s");
+        assert_ne!(duration, "2h");
+        assert_ne!(duration, "not a duration");
+    }
+    #[test]
+    pub fn test_since_until() {
+        let earlier = Utc::now() - Duration::hours(2);
+        let since = ConfigDuration::since(earlier);
+        assert!(since >= ConfigDuration::from_str("2h").unwrap());
+        assert!(since < ConfigDuration::from_str("121m").unwrap());
+
+        let later = Utc::now() + Duration::days(1);
+        let until = ConfigDuration::until(later);
+        assert!(until <= ConfigDuration::from_str("1d").unwrap());
+        assert!(until > ConfigDuration::from_str("23h").unwrap());
+        assert!(ConfigDuration::until(earlier) < ConfigDuration::ZERO);
+
+        let exact = ConfigDuration::in_largest_exact_unit(Duration::minutes(90));
+        assert_eq!(exact.to_string(), "90m");
+        let exact = ConfigDuration::in_largest_exact_unit(Duration::microseconds(1_500));
+        assert_eq!(exact.to_string(), "1ms");
+    }
+    #[test]
+    pub fn test_from_long_name() {
+        assert_eq!(Unit::from_long_name("minutes"), Some(Unit::Minutes));
+        assert_eq!(Unit::from_long_name("Hour"), Some(Unit::Hours));
+        assert_eq!(Unit::from_long_name("secs"), Some(Unit::Seconds));
+        assert_eq!(Unit::from_long_name("weeks"), None);
+        assert_eq!(Unit::from_long_name("m"), None);
+        for unit in Unit::iter() {
+            assert_eq!(Unit::from_long_name(unit.long_name_plural()), Some(unit));
+        }
+    }
+    #[test]
+    pub fn test_error_accessors() {
+        let error = ConfigDuration::from_str("10y").unwrap_err();
+        assert_eq!(error.context(), "Unknown unit");
+        assert!(error.source_error().is_none());
+        assert_eq!(error.span(), Some(2..3));
+        assert_eq!(error.input(), Some("10y"));
+
+        let error =
+            ConfigDuration::from_str("99999999999999999999999999999999999999999s").unwrap_err();
+        assert!(error.source_error().is_some());
+
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(ConfigDuration::from_str(&long).unwrap_err().input(), None);
+    }
+    #[test]
+    pub fn test_unit_index() {
+        assert_eq!(Unit::count(), 5);
+        assert_eq!(Unit::all(), Unit::iter().collect::<Vec<_>>());
+        for (index, unit) in Unit::iter().enumerate() {
+            assert_eq!(unit.index(), index);
+            assert_eq!(Unit::from_index(index), Some(unit));
+        }
+        assert_eq!(Unit::Seconds.index(), 1);
+        assert_eq!(Unit::from_index(5), None);
+    }
+    #[test]
+    pub fn test_percent_of_quota() {
+        let used = ConfigDuration::from_str("45m").unwrap();
+        let total = ConfigDuration::from_str("1h").unwrap();
+        assert_eq!(used.percent_of(&total), 75.0);
+        assert_eq!(used.percent_of(&ConfigDuration::ZERO), 0.0);
+        assert_eq!(ConfigDuration::ZERO.percent_of(&ConfigDuration::ZERO), 0.0);
+        assert_eq!(
+            used.percent_of(&ConfigDuration::from_str("0h").unwrap()),
+            0.0
+        );
+    }
+}
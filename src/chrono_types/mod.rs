@@ -1 +1,4 @@
 pub mod duration;
+pub mod retry_policy;
+#[cfg(feature = "chrono-tz")]
+pub mod time_zone;
@@ -0,0 +1,308 @@
+use chrono::Duration;
+use serde::de::{Error as _, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+use super::duration::ConfigDuration;
+use crate::macros::extend_string_from_and_to;
+
+type AnyError = Box<dyn Error + Send + Sync + 'static>;
+#[derive(Debug, Error)]
+#[error("{0}: {1:?}")]
+pub struct InvalidRetryPolicyError(&'static str, Option<AnyError>);
+
+impl From<(&'static str, AnyError)> for InvalidRetryPolicyError {
+    fn from(value: (&'static str, AnyError)) -> Self {
+        Self(value.0, Some(value.1))
+    }
+}
+impl From<&'static str> for InvalidRetryPolicyError {
+    fn from(value: &'static str) -> Self {
+        Self(value, None)
+    }
+}
+/// Exponential backoff for retrying a failed operation
+///
+/// Written as `{max_attempts}x{initial_delay}..{max_delay}*{multiplier}`.
+/// The multiplier can be left out and defaults to `2`
+///
+/// | Format              | Attempts | Initial Delay | Max Delay | Multiplier |
+/// |---------------------|----------|---------------|-----------|------------|
+/// | "5x100ms..30s*1.5"  | 5        | 100ms         | 30s       | 1.5        |
+/// | "3x1s..1m"          | 3        | 1s            | 1m        | 2          |
+///
+/// # Examples in TOML
+/// ```toml
+/// retry = "5x100ms..30s*1.5"
+/// table = { max_attempts = 5, initial_delay = "100ms", max_delay = "30s", multiplier = 1.5 }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfigRetryPolicy {
+    max_attempts: u32,
+    initial_delay: ConfigDuration,
+    max_delay: ConfigDuration,
+    multiplier: f64,
+}
+impl ConfigRetryPolicy {
+    pub const DEFAULT_MULTIPLIER: f64 = 2.0;
+    /// Creates a new retry policy
+    ///
+    /// Errors if `max_attempts` is zero, `initial_delay > max_delay` or `multiplier` is less than 1
+    pub fn new(
+        max_attempts: u32,
+        initial_delay: ConfigDuration,
+        max_delay: ConfigDuration,
+        multiplier: f64,
+    ) -> Result<Self, InvalidRetryPolicyError> {
+        if max_attempts == 0 {
+            return Err(InvalidRetryPolicyError::from(
+                "Max attempts must be greater than zero",
+            ));
+        }
+        if initial_delay > max_delay {
+            return Err(InvalidRetryPolicyError::from(
+                "Initial delay is greater than the max delay",
+            ));
+        }
+        // Also rejects NaN
+        if !(multiplier >= 1.0 && multiplier.is_finite()) {
+            return Err(InvalidRetryPolicyError::from(
+                "Multiplier must be a finite number of at least 1",
+            ));
+        }
+        Ok(Self {
+            max_attempts,
+            initial_delay,
+            max_delay,
+            multiplier,
+        })
+    }
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    pub fn initial_delay(&self) -> ConfigDuration {
+        self.initial_delay
+    }
+    pub fn max_delay(&self) -> ConfigDuration {
+        self.max_delay
+    }
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+    /// The delay before retry number `attempt`. Starting at zero for the first retry
+    ///
+    /// `initial_delay * multiplier ^ attempt` rounded to the millisecond. Never more than `max_delay`
+    pub fn delay_for(&self, attempt: u32) -> ConfigDuration {
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        let delay = self.initial_delay.num_milliseconds() as f64 * self.multiplier.powi(exponent);
+        if delay >= self.max_delay.num_milliseconds() as f64 {
+            return self.max_delay;
+        }
+        ConfigDuration::from(Duration::milliseconds(delay.round() as i64))
+    }
+}
+impl Display for ConfigRetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}x{}..{}*{}",
+            self.max_attempts, self.initial_delay, self.max_delay, self.multiplier
+        )
+    }
+}
+impl FromStr for ConfigRetryPolicy {
+    type Err = InvalidRetryPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_duration = |value: &str| {
+            ConfigDuration::from_str(value.trim())
+                .map_err(|v| InvalidRetryPolicyError::from(("Invalid Duration", v.into())))
+        };
+        let (max_attempts, delays) = s.split_once('x').ok_or(InvalidRetryPolicyError::from(
+            "Missing the x after max attempts",
+        ))?;
+        let max_attempts = max_attempts
+            .trim()
+            .parse::<u32>()
+            .map_err(|v| InvalidRetryPolicyError::from(("Invalid Max Attempts", v.into())))?;
+        let (delays, multiplier) = match delays.split_once('*') {
+            Some((delays, multiplier)) => {
+                let multiplier = multiplier
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|v| InvalidRetryPolicyError::from(("Invalid Multiplier", v.into())))?;
+                (delays, multiplier)
+            }
+            None => (delays, Self::DEFAULT_MULTIPLIER),
+        };
+        let (initial_delay, max_delay) =
+            delays
+                .split_once("..")
+                .ok_or(InvalidRetryPolicyError::from(
+                    "Missing the .. between the delays",
+                ))?;
+        Self::new(
+            max_attempts,
+            parse_duration(initial_delay)?,
+            parse_duration(max_delay)?,
+            multiplier,
+        )
+    }
+}
+extend_string_from_and_to!(ConfigRetryPolicy, InvalidRetryPolicyError);
+
+impl Serialize for ConfigRetryPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for ConfigRetryPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct RetryPolicyVisitor;
+        impl<'de> Visitor<'de> for RetryPolicyVisitor {
+            type Value = ConfigRetryPolicy;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a retry policy string or a table")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                ConfigRetryPolicy::from_str(v).map_err(E::custom)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                const FIELDS: &[&str] =
+                    &["max_attempts", "initial_delay", "max_delay", "multiplier"];
+                let mut max_attempts = None;
+                let mut initial_delay = None;
+                let mut max_delay = None;
+                let mut multiplier = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "max_attempts" => max_attempts = Some(map.next_value::<u32>()?),
+                        "initial_delay" => {
+                            initial_delay = Some(map.next_value::<ConfigDuration>()?)
+                        }
+                        "max_delay" => max_delay = Some(map.next_value::<ConfigDuration>()?),
+                        "multiplier" => multiplier = Some(map.next_value::<f64>()?),
+                        other => return Err(A::Error::unknown_field(other, FIELDS)),
+                    }
+                }
+                ConfigRetryPolicy::new(
+                    max_attempts.ok_or_else(|| A::Error::missing_field("max_attempts"))?,
+                    initial_delay.ok_or_else(|| A::Error::missing_field("initial_delay"))?,
+                    max_delay.ok_or_else(|| A::Error::missing_field("max_delay"))?,
+                    multiplier.unwrap_or(ConfigRetryPolicy::DEFAULT_MULTIPLIER),
+                )
+                .map_err(A::Error::custom)
+            }
+        }
+        deserializer.deserialize_any(RetryPolicyVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{ONE_MINUTE, ONE_SECOND};
+
+    fn duration(value: &str) -> ConfigDuration {
+        ConfigDuration::from_str(value).unwrap()
+    }
+    #[test]
+    pub fn test_parse() {
+        let policy = ConfigRetryPolicy::from_str("5x100ms..30s*1.5").unwrap();
+        assert_eq!(policy.max_attempts(), 5);
+        assert_eq!(policy.initial_delay(), duration("100ms"));
+        assert_eq!(policy.max_delay(), duration("30s"));
+        assert_eq!(policy.multiplier(), 1.5);
+        assert_eq!(policy.to_string(), "5x100ms..30s*1.5");
+
+        let policy = ConfigRetryPolicy::from_str("3x1s..1m").unwrap();
+        assert_eq!(policy.initial_delay(), ONE_SECOND);
+        assert_eq!(policy.max_delay(), ONE_MINUTE);
+        assert_eq!(policy.multiplier(), 2.0);
+        assert_eq!(policy.to_string(), "3x1s..1m*2");
+        assert_eq!(
+            ConfigRetryPolicy::from_str(&policy.to_string()).unwrap(),
+            policy
+        );
+
+        for invalid in [
+            "0x1s..1m",
+            "3x1m..1s",
+            "3x1s..1m*0.5",
+            "3x1s..1m*NaN",
+            "3x1s..1m*inf",
+            "3x1s",
+            "1s..1m",
+            "3x1s..1q",
+            "-1x1s..1m",
+        ] {
+            assert!(
+                ConfigRetryPolicy::from_str(invalid).is_err(),
+                "Input: {:?}",
+                invalid
+            );
+        }
+    }
+    #[test]
+    pub fn test_delay_for() {
+        let policy = ConfigRetryPolicy::from_str("5x100ms..1s*1.5").unwrap();
+        assert_eq!(policy.delay_for(0), duration("100ms"));
+        assert_eq!(policy.delay_for(1), duration("150ms"));
+        assert_eq!(policy.delay_for(2), duration("225ms"));
+        assert_eq!(policy.delay_for(5), duration("759ms"));
+        assert_eq!(policy.delay_for(6), ONE_SECOND);
+        assert_eq!(policy.delay_for(u32::MAX), ONE_SECOND);
+
+        let constant = ConfigRetryPolicy::from_str("3x1s..1m*1").unwrap();
+        assert_eq!(constant.delay_for(100), ONE_SECOND);
+    }
+    #[test]
+    pub fn test_serde() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Config {
+            string: ConfigRetryPolicy,
+            table: ConfigRetryPolicy,
+        }
+        let config: Config = serde_json::from_str(
+            r#"{
+                "string": "5x100ms..30s*1.5",
+                "table": {"max_attempts": 3, "initial_delay": "1s", "max_delay": "1m"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.string,
+            ConfigRetryPolicy::from_str("5x100ms..30s*1.5").unwrap()
+        );
+        assert_eq!(
+            config.table,
+            ConfigRetryPolicy::from_str("3x1s..1m").unwrap()
+        );
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"string":"5x100ms..30s*1.5","table":"3x1s..1m*2"}"#
+        );
+        assert!(serde_json::from_str::<Config>(
+            r#"{"string": "5x1s..1m", "table": {"max_attempts": 0, "initial_delay": "1s", "max_delay": "1m"}}"#
+        )
+        .is_err());
+    }
+}
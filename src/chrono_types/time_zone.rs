@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use derive_more::derive::{AsRef, Deref, From, Into};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::macros::{extend_string_from_and_to, serde_via_string_types};
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("Unknown time zone {0:?}. Expected an IANA name such as America/New_York")]
+pub struct InvalidTimeZoneError(pub String);
+
+/// A time zone from its IANA name
+///
+/// # Examples in TOML
+/// ```toml
+/// time_zone = "America/New_York"
+/// log_time_zone = "UTC"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, From, Into, AsRef, Deref)]
+pub struct ConfigTimeZone(Tz);
+serde_via_string_types!(ConfigTimeZone);
+
+impl ConfigTimeZone {
+    pub const UTC: ConfigTimeZone = ConfigTimeZone(Tz::UTC);
+
+    /// The time zone of the system. Read from the OS each time it is called
+    ///
+    /// There is no `LOCAL` constant because it can only be found at runtime
+    pub fn local() -> Result<ConfigTimeZone, InvalidTimeZoneError> {
+        let name = iana_time_zone::get_timezone()
+            .map_err(|err| InvalidTimeZoneError(format!("local time zone: {}", err)))?;
+        Self::from_str(&name)
+    }
+    /// The current time in this time zone
+    pub fn now(&self) -> DateTime<Tz> {
+        Utc::now().with_timezone(&self.0)
+    }
+    pub fn into_inner(self) -> Tz {
+        self.0
+    }
+}
+impl Display for ConfigTimeZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.name())
+    }
+}
+impl FromStr for ConfigTimeZone {
+    type Err = InvalidTimeZoneError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tz::from_str(s)
+            .map(ConfigTimeZone)
+            .map_err(|_| InvalidTimeZoneError(s.to_owned()))
+    }
+}
+extend_string_from_and_to!(ConfigTimeZone, InvalidTimeZoneError);
+impl Default for ConfigTimeZone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Offset;
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    pub fn test_parse() {
+        for name in ["America/New_York", "UTC", "Europe/Berlin"] {
+            let time_zone = ConfigTimeZone::from_str(name).unwrap();
+            assert_eq!(time_zone.to_string(), name);
+        }
+        assert_eq!(
+            ConfigTimeZone::from_str("UTC").unwrap(),
+            ConfigTimeZone::UTC
+        );
+        assert_eq!(
+            Tz::from(ConfigTimeZone::from_str("Europe/Berlin").unwrap()),
+            Tz::Europe__Berlin
+        );
+        let error = ConfigTimeZone::from_str("Mars/Olympus_Mons").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Unknown time zone "Mars/Olympus_Mons". Expected an IANA name such as America/New_York"#
+        );
+    }
+    #[test]
+    pub fn test_now() {
+        let now = ConfigTimeZone::UTC.now();
+        assert_eq!(now.offset().fix().local_minus_utc(), 0);
+        assert!((Utc::now() - now.with_timezone(&Utc)).num_seconds() < 5);
+    }
+    #[test]
+    pub fn test_serde() {
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct Config {
+            time_zone: ConfigTimeZone,
+        }
+        let config: Config = serde_json::from_str(r#"{"time_zone":"America/New_York"}"#).unwrap();
+        assert_eq!(*config.time_zone, Tz::America__New_York);
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"time_zone":"America/New_York"}"#
+        );
+        assert!(serde_json::from_str::<Config>(r#"{"time_zone":"Nowhere"}"#).is_err());
+    }
+}
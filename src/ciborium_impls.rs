@@ -0,0 +1,89 @@
+//! [CBOR](https://cbor.io) helpers using [ciborium](https://docs.rs/ciborium)
+//!
+//! Sizes are encoded as a text string. The same as JSON. With the `cbor-integer` feature they are
+//! encoded as an integer byte count instead. Decoding accepts either form.
+//!
+//! Durations are always encoded as a text string.
+use ciborium::de::Error as DecodeError;
+use ciborium::Value;
+use std::str::FromStr;
+
+use crate::size_config::ConfigSize;
+
+impl ConfigSize {
+    /// Encodes the size as CBOR
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        #[cfg(feature = "cbor-integer")]
+        let result = ciborium::ser::into_writer(&(self.get_as_bytes() as u64), &mut bytes);
+        #[cfg(not(feature = "cbor-integer"))]
+        let result = ciborium::ser::into_writer(self, &mut bytes);
+        result.expect("Encoding into a Vec can not fail");
+        bytes
+    }
+    /// Decodes a size from either a CBOR text string or integer byte count
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, DecodeError<std::io::Error>> {
+        match ciborium::de::from_reader(bytes)? {
+            Value::Integer(integer) => usize::try_from(i128::from(integer))
+                .map(ConfigSize::from)
+                .map_err(|err| DecodeError::Semantic(None, err.to_string())),
+            Value::Text(text) => ConfigSize::from_str(&text)
+                .map_err(|err| DecodeError::Semantic(None, err.to_string())),
+            _ => Err(DecodeError::Semantic(
+                None,
+                "Expected a text string or integer".to_owned(),
+            )),
+        }
+    }
+}
+#[cfg(feature = "chrono")]
+impl crate::chrono_types::duration::ConfigDuration {
+    /// Encodes the duration as a CBOR text string
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(self, &mut bytes).expect("Encoding into a Vec can not fail");
+        bytes
+    }
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, DecodeError<std::io::Error>> {
+        ciborium::de::from_reader(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<T: serde::Serialize>(value: T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(&value, &mut bytes).unwrap();
+        bytes
+    }
+    #[test]
+    pub fn test_size() {
+        let size = ConfigSize::from_str("512MiB").unwrap();
+        let bytes = size.to_cbor();
+        #[cfg(feature = "cbor-integer")]
+        assert_eq!(bytes, encode(512u64 * 1024 * 1024));
+        #[cfg(not(feature = "cbor-integer"))]
+        assert_eq!(bytes, encode("512MiB"));
+        assert_eq!(ConfigSize::from_cbor(&bytes).unwrap(), size);
+
+        assert_eq!(ConfigSize::from_cbor(&encode("512MiB")).unwrap(), size);
+        assert_eq!(
+            ConfigSize::from_cbor(&encode(1024u64)).unwrap(),
+            ConfigSize::new_from_kibibytes(1)
+        );
+        assert!(ConfigSize::from_cbor(&encode(-1i64)).is_err());
+        assert!(ConfigSize::from_cbor(&encode("512XB")).is_err());
+        assert!(ConfigSize::from_cbor(&encode(true)).is_err());
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        use crate::chrono_types::duration::ConfigDuration;
+        let duration = ConfigDuration::from_str("30s").unwrap();
+        let bytes = duration.to_cbor();
+        assert_eq!(bytes, encode("30s"));
+        assert_eq!(ConfigDuration::from_cbor(&bytes).unwrap(), duration);
+    }
+}
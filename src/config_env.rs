@@ -0,0 +1,124 @@
+use regex::{Captures, Regex};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+use crate::macros::{extend_string_from_and_to, serde_via_string_types};
+
+static ENV_VAR_REGEX: OnceLock<Regex> = OnceLock::new();
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("Environment variable {0} is not set")]
+pub struct MissingEnvVarError(pub String);
+
+/// A string that can reference environment variables. They are resolved when parsed
+///
+/// | Placeholder | Replaced With                |
+/// |-------------|------------------------------|
+/// | `${VAR}`    | The value of `VAR`           |
+/// | `$VAR`      | The value of `VAR`           |
+/// | `$$`        | A literal `$`                |
+///
+/// Display and serialization use the raw template. So resolved secrets are not written back out.
+///
+/// # Examples in TOML
+/// ```toml
+/// database_url = "${DATABASE_URL}"
+/// data_dir = "$HOME/data"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConfigEnvVar {
+    raw_template: String,
+    resolved: String,
+}
+serde_via_string_types!(ConfigEnvVar);
+impl ConfigEnvVar {
+    /// The value with all environment variables replaced
+    pub fn resolved_value(&self) -> &str {
+        &self.resolved
+    }
+    /// The value as it was written in the config
+    pub fn raw_template(&self) -> &str {
+        &self.raw_template
+    }
+    fn regex() -> &'static Regex {
+        ENV_VAR_REGEX.get_or_init(|| {
+            Regex::new(
+                r"\$\$|\$\{(?<braced>[A-Za-z_][A-Za-z0-9_]*)\}|\$(?<bare>[A-Za-z_][A-Za-z0-9_]*)",
+            )
+            .unwrap()
+        })
+    }
+}
+impl Display for ConfigEnvVar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw_template)
+    }
+}
+impl FromStr for ConfigEnvVar {
+    type Err = MissingEnvVarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut resolved = String::with_capacity(s.len());
+        let mut last_end = 0;
+        for captures in Self::regex().captures_iter(s) {
+            let placeholder = captures.get(0).unwrap();
+            resolved.push_str(&s[last_end..placeholder.start()]);
+            resolved.push_str(&resolve(&captures)?);
+            last_end = placeholder.end();
+        }
+        resolved.push_str(&s[last_end..]);
+        Ok(Self {
+            raw_template: s.to_owned(),
+            resolved,
+        })
+    }
+}
+fn resolve(captures: &Captures) -> Result<String, MissingEnvVarError> {
+    let Some(name) = captures.name("braced").or_else(|| captures.name("bare")) else {
+        return Ok("$".to_owned());
+    };
+    std::env::var(name.as_str()).map_err(|_| MissingEnvVarError(name.as_str().to_owned()))
+}
+extend_string_from_and_to!(ConfigEnvVar, MissingEnvVarError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    pub struct SerdeTest {
+        pub value: ConfigEnvVar,
+    }
+    #[test]
+    pub fn test_resolve() {
+        std::env::set_var("TUXS_CONFIG_ENV_TEST_USER", "tux");
+        let value = ConfigEnvVar::from_str("${TUXS_CONFIG_ENV_TEST_USER}").unwrap();
+        assert_eq!(value.resolved_value(), "tux");
+        assert_eq!(value.raw_template(), "${TUXS_CONFIG_ENV_TEST_USER}");
+
+        let value = ConfigEnvVar::from_str("/home/$TUXS_CONFIG_ENV_TEST_USER/data").unwrap();
+        assert_eq!(value.resolved_value(), "/home/tux/data");
+
+        let value = ConfigEnvVar::from_str("costs $$5").unwrap();
+        assert_eq!(value.resolved_value(), "costs $5");
+        let value = ConfigEnvVar::from_str("no variables").unwrap();
+        assert_eq!(value.resolved_value(), "no variables");
+    }
+    #[test]
+    pub fn test_missing() {
+        let error = ConfigEnvVar::from_str("${TUXS_CONFIG_ENV_TEST_MISSING}").unwrap_err();
+        assert_eq!(error.0, "TUXS_CONFIG_ENV_TEST_MISSING");
+    }
+    #[test]
+    pub fn test_serde() {
+        std::env::set_var("TUXS_CONFIG_ENV_TEST_SECRET", "hunter2");
+        let test: SerdeTest =
+            serde_json::from_str(r#"{"value": "${TUXS_CONFIG_ENV_TEST_SECRET}"}"#).unwrap();
+        assert_eq!(test.value.resolved_value(), "hunter2");
+        let string = serde_json::to_string(&test).unwrap();
+        assert_eq!(string, r#"{"value":"${TUXS_CONFIG_ENV_TEST_SECRET}"}"#);
+    }
+}
@@ -0,0 +1,73 @@
+//! The parts shared by [size_config::Unit](crate::size_config::Unit) and [chrono_types::duration::Unit](crate::chrono_types::duration::Unit)
+//!
+//! Allows code that works over either kind of unit
+//! ```rust
+//! use tuxs_config_types::config_unit::ConfigUnit;
+//! use tuxs_config_types::SizeUnit;
+//!
+//! fn names<U: ConfigUnit>() -> Vec<&'static str> {
+//!     U::iter().map(|unit| unit.strum_name()).collect()
+//! }
+//! assert_eq!(names::<SizeUnit>()[..3], ["B", "KiB", "MiB"]);
+//! ```
+#[cfg(feature = "regex")]
+use regex::Regex;
+use strum::IntoEnumIterator;
+
+pub trait ConfigUnit: Sized + Copy + 'static + IntoEnumIterator + Into<&'static str> {
+    /// The name used when parsing and displaying. Such as `KiB` or `ms`
+    fn strum_name(&self) -> &'static str {
+        (*self).into()
+    }
+    /// The pattern used to parse a value with this unit
+    #[cfg(feature = "regex")]
+    fn create_regex_string() -> String;
+    /// Every unit name joined by `|`. For the unit group of the parsing regex
+    fn unit_options() -> String {
+        Self::iter()
+            .map(|unit| unit.strum_name())
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+    /// Compiles [ConfigUnit::create_regex_string]
+    ///
+    /// # Panics
+    /// If the pattern is invalid. Which is a bug
+    #[cfg(feature = "regex")]
+    fn build_regex() -> Regex {
+        Regex::new(&Self::create_regex_string())
+            .map_err(|err| {
+                format!(
+                    "Unable to Build {} Regex. Please Report: {:?}. \n{:?}",
+                    std::any::type_name::<Self>(),
+                    Self::create_regex_string(),
+                    err
+                )
+            })
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::size_config;
+
+    #[test]
+    pub fn test_unit_options() {
+        assert_eq!(
+            size_config::Unit::unit_options(),
+            "B|KiB|MiB|b|Kib|Mib|GiB|TiB"
+        );
+        assert_eq!(size_config::Unit::Kibibytes.strum_name(), "KiB");
+        #[cfg(feature = "regex")]
+        assert!(size_config::Unit::build_regex().is_match("512MiB"));
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration_unit() {
+        use crate::chrono_types::duration;
+        assert_eq!(duration::Unit::unit_options(), "ms|s|m|h|d");
+        assert!(duration::Unit::build_regex().is_match("-1.5h"));
+    }
+}
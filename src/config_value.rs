@@ -0,0 +1,315 @@
+use serde::de::Visitor;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use strum::EnumIs;
+use thiserror::Error;
+
+#[cfg(feature = "chrono")]
+use crate::chrono_types::duration::ConfigDuration;
+use crate::size_config::ConfigSize;
+
+type AnyError = Box<dyn Error + Send + Sync + 'static>;
+#[derive(Debug, Error)]
+#[error("{0}: {1:?}")]
+pub struct InvalidConfigValueError(&'static str, Option<AnyError>);
+
+impl From<(&'static str, AnyError)> for InvalidConfigValueError {
+    fn from(value: (&'static str, AnyError)) -> Self {
+        Self(value.0, Some(value.1))
+    }
+}
+impl From<&'static str> for InvalidConfigValueError {
+    fn from(value: &'static str) -> Self {
+        Self(value, None)
+    }
+}
+/// A config value that can be any of the config types
+///
+/// Strings are parsed by trying each variant in order. Size, Duration, Bool, Integer, Float and then Text.
+/// Because a size without a unit is in bytes, a string such as `"100"` is parsed as a size.
+///
+/// Native booleans, integers and floats are deserialized into their matching variant.
+///
+/// | Input      | Variant  |
+/// |------------|----------|
+/// | "512MiB"   | Size     |
+/// | "30s"      | Duration |
+/// | "true"     | Bool     |
+/// | "-5"       | Integer  |
+/// | "1.5"      | Float    |
+/// | "hello"    | Text     |
+#[derive(Debug, Clone, PartialEq, EnumIs)]
+#[non_exhaustive]
+pub enum ConfigValue {
+    Size(ConfigSize),
+    #[cfg(feature = "chrono")]
+    Duration(ConfigDuration),
+    Text(String),
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+}
+impl ConfigValue {
+    pub fn as_size(&self) -> Option<&ConfigSize> {
+        match self {
+            ConfigValue::Size(size) => Some(size),
+            _ => None,
+        }
+    }
+    #[cfg(feature = "chrono")]
+    pub fn as_duration(&self) -> Option<&ConfigDuration> {
+        match self {
+            ConfigValue::Duration(duration) => Some(duration),
+            _ => None,
+        }
+    }
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            ConfigValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ConfigValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            ConfigValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            ConfigValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+impl Display for ConfigValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValue::Size(size) => size.fmt(f),
+            #[cfg(feature = "chrono")]
+            ConfigValue::Duration(duration) => duration.fmt(f),
+            ConfigValue::Text(text) => text.fmt(f),
+            ConfigValue::Bool(value) => value.fmt(f),
+            ConfigValue::Integer(value) => value.fmt(f),
+            ConfigValue::Float(value) => value.fmt(f),
+        }
+    }
+}
+impl FromStr for ConfigValue {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(size) = ConfigSize::from_str(s) {
+            return Ok(ConfigValue::Size(size));
+        }
+        // Unitless numbers such as -5 or 1.000 are left for the integer and float variants
+        #[cfg(feature = "chrono")]
+        if let Ok(duration) = ConfigDuration::from_str_require_unit(s) {
+            return Ok(ConfigValue::Duration(duration));
+        }
+        if let Ok(value) = bool::from_str(s) {
+            return Ok(ConfigValue::Bool(value));
+        }
+        if let Ok(value) = i64::from_str(s) {
+            return Ok(ConfigValue::Integer(value));
+        }
+        if let Ok(value) = f64::from_str(s) {
+            return Ok(ConfigValue::Float(value));
+        }
+        Ok(ConfigValue::Text(s.to_owned()))
+    }
+}
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        ConfigValue::from_str(value).unwrap_or_else(|never| match never {})
+    }
+}
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        ConfigValue::from(value.as_str())
+    }
+}
+
+macro_rules! config_value_variant {
+    ($variant:ident, $type:ty, $name:literal) => {
+        impl From<$type> for ConfigValue {
+            fn from(value: $type) -> Self {
+                ConfigValue::$variant(value)
+            }
+        }
+        impl TryFrom<ConfigValue> for $type {
+            type Error = InvalidConfigValueError;
+
+            fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
+                match value {
+                    ConfigValue::$variant(value) => Ok(value),
+                    _ => Err(InvalidConfigValueError::from(concat!(
+                        "Config value is not a ",
+                        $name
+                    ))),
+                }
+            }
+        }
+    };
+}
+config_value_variant!(Size, ConfigSize, "size");
+#[cfg(feature = "chrono")]
+config_value_variant!(Duration, ConfigDuration, "duration");
+config_value_variant!(Bool, bool, "bool");
+config_value_variant!(Integer, i64, "integer");
+config_value_variant!(Float, f64, "float");
+impl TryFrom<ConfigValue> for String {
+    type Error = InvalidConfigValueError;
+
+    fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
+        match value {
+            ConfigValue::Text(text) => Ok(text),
+            _ => Err(InvalidConfigValueError::from("Config value is not text")),
+        }
+    }
+}
+
+impl Serialize for ConfigValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ConfigValue::Size(size) => size.serialize(serializer),
+            #[cfg(feature = "chrono")]
+            ConfigValue::Duration(duration) => duration.serialize(serializer),
+            ConfigValue::Text(text) => text.serialize(serializer),
+            ConfigValue::Bool(value) => value.serialize(serializer),
+            ConfigValue::Integer(value) => value.serialize(serializer),
+            ConfigValue::Float(value) => value.serialize(serializer),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for ConfigValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ConfigValueVisitor;
+        impl Visitor<'_> for ConfigValueVisitor {
+            type Value = ConfigValue;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a string, boolean or number")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConfigValue::Bool(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConfigValue::Integer(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i64::try_from(v)
+                    .map(ConfigValue::Integer)
+                    .map_err(E::custom)
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConfigValue::Float(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConfigValue::from(v))
+            }
+        }
+        deserializer.deserialize_any(ConfigValueVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    pub fn test_from_str() {
+        let value = ConfigValue::from("512MiB");
+        assert_eq!(value.as_size(), Some(&ConfigSize::new_from_mebibytes(512)));
+        assert_eq!(ConfigValue::from("true"), ConfigValue::Bool(true));
+        assert_eq!(ConfigValue::from("-5"), ConfigValue::Integer(-5));
+        assert_eq!(ConfigValue::from("1.5"), ConfigValue::Float(1.5));
+        assert_eq!(
+            ConfigValue::from("hello"),
+            ConfigValue::Text("hello".to_owned())
+        );
+        assert_eq!(ConfigValue::from("hello").to_string(), "hello");
+        assert_eq!(value.to_string(), "512MiB");
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        let value = ConfigValue::from("30s");
+        assert!(value.is_duration());
+        assert_eq!(value.as_duration().unwrap().num_seconds(), 30);
+        assert_eq!(value.to_string(), "30s");
+        let duration = ConfigDuration::try_from(value).unwrap();
+        assert_eq!(duration.num_seconds(), 30);
+    }
+    #[test]
+    pub fn test_try_from() {
+        let size = ConfigSize::try_from(ConfigValue::from("1KiB")).unwrap();
+        assert_eq!(size, ConfigSize::new_from_kibibytes(1));
+        assert!(ConfigSize::try_from(ConfigValue::from("hello")).is_err());
+        assert!(bool::try_from(ConfigValue::from("512MiB")).is_err());
+        assert_eq!(
+            String::try_from(ConfigValue::from("hello")).unwrap(),
+            "hello"
+        );
+    }
+    #[test]
+    pub fn test_serde() {
+        let map: HashMap<String, ConfigValue> = serde_json::from_str(
+            r#"{"size": "512MiB", "enabled": true, "count": 5, "ratio": 0.5, "name": "hello"}"#,
+        )
+        .unwrap();
+        assert!(map["size"].is_size());
+        assert_eq!(map["enabled"], ConfigValue::Bool(true));
+        assert_eq!(map["count"], ConfigValue::Integer(5));
+        assert_eq!(map["ratio"], ConfigValue::Float(0.5));
+        assert_eq!(map["name"], ConfigValue::Text("hello".to_owned()));
+
+        let value = serde_json::to_string(&map["size"]).unwrap();
+        assert_eq!(value, r#""512MiB""#);
+        let value = serde_json::to_string(&map["count"]).unwrap();
+        assert_eq!(value, "5");
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_signed_duration() {
+        let value = ConfigValue::from("-1.5h");
+        assert_eq!(value.as_duration().unwrap().num_minutes(), -90);
+        assert_eq!(ConfigValue::from("1.000"), ConfigValue::Float(1.0));
+    }
+}
@@ -1,11 +1,73 @@
 #[cfg(feature = "chrono")]
 pub mod chrono_types;
+#[cfg(feature = "regex")]
+pub mod config_env;
+pub mod config_unit;
+pub mod config_value;
+pub mod memory_limit;
+pub mod quantity;
 pub mod size_config;
+pub mod validation;
+
+#[cfg(feature = "approx")]
+pub mod approx_impls;
+#[cfg(feature = "bytes")]
+pub mod bytes_impls;
+#[cfg(feature = "bytesize")]
+pub mod bytesize_impls;
+#[cfg(feature = "ciborium")]
+pub mod ciborium_impls;
+#[cfg(feature = "num-traits")]
+pub mod num_traits_impls;
+#[cfg(feature = "postcard")]
+pub mod postcard_impls;
+#[cfg(feature = "rmp-serde")]
+pub mod rmp_serde_impls;
+#[cfg(feature = "serde_with")]
+pub mod serde_with;
+#[cfg(feature = "sqlx")]
+pub mod sqlx_impls;
+#[cfg(feature = "sys-info")]
+pub mod sys_info;
+#[cfg(feature = "time")]
+pub mod time_impls;
+#[cfg(feature = "tokio")]
+pub mod tokio_ext;
+#[cfg(feature = "validator")]
+pub mod validators;
 
 pub(crate) mod macros;
+#[cfg(any(test, feature = "test-helpers"))]
+pub mod test_helpers;
+
+/// [size_config::Unit] under a name that does not clash with `DurationUnit`
+///
+/// Both units can be imported together without qualifying the path
+/// ```rust
+/// use tuxs_config_types::SizeUnit;
+/// assert_eq!(SizeUnit::Mebibytes.to_string(), "MiB");
+/// ```
+pub type SizeUnit = size_config::Unit;
+/// [chrono_types::duration::Unit] under a name that does not clash with [SizeUnit]
+#[cfg(feature = "chrono")]
+pub type DurationUnit = chrono_types::duration::Unit;
 #[cfg(test)]
 mod tests {
-
     #[test]
     fn test() {}
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_unit_aliases() {
+        use super::*;
+        use chrono_types::duration::ConfigDuration;
+        use size_config::ConfigSize;
+
+        let size = ConfigSize {
+            size: 512,
+            unit: SizeUnit::Mebibytes,
+        };
+        let duration = ConfigDuration::parse("30s").unwrap();
+        assert_eq!(size, ConfigSize::new_from_mebibytes(512));
+        assert_eq!(duration.unit, DurationUnit::Seconds);
+    }
 }
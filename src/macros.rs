@@ -1,4 +1,211 @@
+/// Implements serde through [Display](std::fmt::Display) and [FromStr](std::str::FromStr)
+///
+/// With `default = expr` a missing field deserializes to `expr` without needing `#[serde(default)]`.
+/// A `null` or unit value also becomes the default
 macro_rules! serde_via_string_types {
+    (
+        @serialize $type:ty
+    ) => {
+        impl serde::Serialize for $type {
+            fn serialize<S>(
+                &self,
+                serializer: S,
+            ) -> Result<<S as serde::Serializer>::Ok, <S as serde::Serializer>::Error>
+            where
+                S: serde::Serializer,
+            {
+                self.to_string().serialize(serializer)
+            }
+        }
+    };
+    (
+        $type:ty
+    ) => {
+        const _: () = {
+            serde_via_string_types! { @serialize $type }
+
+            impl<'de> serde::Deserialize<'de> for $type {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    let s = String::deserialize(deserializer)?;
+                    Self::from_str(&s).map_err(serde::de::Error::custom)
+                }
+            }
+        };
+    };
+    (
+        $type:ty, default = $default:expr
+    ) => {
+        const _: () = {
+            serde_via_string_types! { @serialize $type }
+
+            impl<'de> serde::Deserialize<'de> for $type {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    struct Visitor;
+                    impl<'de> serde::de::Visitor<'de> for Visitor {
+                        type Value = $type;
+
+                        fn expecting(
+                            &self,
+                            formatter: &mut std::fmt::Formatter,
+                        ) -> std::fmt::Result {
+                            formatter.write_str("a string or nothing")
+                        }
+                        // Serde deserializes a missing field as none. The same way a missing Option is None
+                        fn visit_none<E>(self) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            Ok($default)
+                        }
+                        fn visit_unit<E>(self) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            Ok($default)
+                        }
+                        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                        where
+                            D: serde::Deserializer<'de>,
+                        {
+                            let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                            <$type>::from_str(&s).map_err(serde::de::Error::custom)
+                        }
+                        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            <$type>::from_str(v).map_err(E::custom)
+                        }
+                    }
+                    // Formats that are not self describing such as postcard only ever contain the string
+                    if deserializer.is_human_readable() {
+                        deserializer.deserialize_option(Visitor)
+                    } else {
+                        deserializer.deserialize_str(Visitor)
+                    }
+                }
+            }
+        };
+    };
+}
+pub(crate) use serde_via_string_types;
+
+/// Same as [serde_via_string_types] but numbers are also accepted and converted with `$from_number`. A `fn(u64) -> $type`
+///
+/// Negative numbers are an error. Also supports `default = expr` the same way as [serde_via_string_types]
+macro_rules! serde_via_string_or_number_types {
+    (
+        $type:ty, $from_number:expr
+    ) => {
+        serde_via_string_or_number_types!(@impl $type, $from_number, deserialize_any, {});
+    };
+    (
+        $type:ty, $from_number:expr, default = $default:expr
+    ) => {
+        serde_via_string_or_number_types!(@impl $type, $from_number, deserialize_option, {
+            // Serde deserializes a missing field as none. The same way a missing Option is None
+            fn visit_none<E>(self) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok($default)
+            }
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok($default)
+            }
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                serde_via_string_or_number_types!(@deserialize deserialize_any, deserializer, self)
+            }
+        });
+    };
+    // Formats that are not self describing such as postcard or bincode can not deserialize_any.
+    // They only ever contain the string that was serialized. Never a missing field or a number
+    (
+        @deserialize deserialize_any, $deserializer:ident, $visitor:expr
+    ) => {
+        if $deserializer.is_human_readable() {
+            $deserializer.deserialize_any($visitor)
+        } else {
+            $deserializer.deserialize_str($visitor)
+        }
+    };
+    (
+        @deserialize deserialize_option, $deserializer:ident, $visitor:expr
+    ) => {
+        if $deserializer.is_human_readable() {
+            $deserializer.deserialize_option($visitor)
+        } else {
+            $deserializer.deserialize_str($visitor)
+        }
+    };
+    (
+        @impl $type:ty, $from_number:expr, $deserialize:ident, { $($visitor:tt)* }
+    ) => {
+        const _: () = {
+            $crate::macros::serde_via_string_types!(@serialize $type);
+
+            impl<'de> serde::Deserialize<'de> for $type {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    struct Visitor;
+                    impl<'de> serde::de::Visitor<'de> for Visitor {
+                        type Value = $type;
+
+                        fn expecting(
+                            &self,
+                            formatter: &mut std::fmt::Formatter,
+                        ) -> std::fmt::Result {
+                            formatter.write_str("a string or a number")
+                        }
+                        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            <$type>::from_str(v).map_err(E::custom)
+                        }
+                        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            let from_number: fn(u64) -> $type = $from_number;
+                            Ok(from_number(v))
+                        }
+                        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            let v = u64::try_from(v).map_err(E::custom)?;
+                            self.visit_u64(v)
+                        }
+                        $($visitor)*
+                    }
+                    serde_via_string_or_number_types!(@deserialize $deserialize, deserializer, Visitor)
+                }
+            }
+        };
+    };
+}
+pub(crate) use serde_via_string_or_number_types;
+
+/// Same as [serde_via_string_types] but serializes with [serde::Serializer::collect_str].
+/// Avoiding the intermediate `String` for serializers that can write `Display` directly
+///
+/// Deserializing also accepts integers. Which are parsed the same as a string without a unit
+macro_rules! serde_via_display {
     (
         $type:ty
     ) => {
@@ -11,7 +218,7 @@ macro_rules! serde_via_string_types {
                 where
                     S: serde::Serializer,
                 {
-                    self.to_string().serialize(serializer)
+                    serializer.collect_str(self)
                 }
             }
 
@@ -20,14 +227,48 @@ macro_rules! serde_via_string_types {
                 where
                     D: serde::Deserializer<'de>,
                 {
-                    let s = String::deserialize(deserializer)?;
-                    Self::from_str(&s).map_err(serde::de::Error::custom)
+                    struct Visitor;
+                    impl<'de> serde::de::Visitor<'de> for Visitor {
+                        type Value = $type;
+
+                        fn expecting(
+                            &self,
+                            formatter: &mut std::fmt::Formatter,
+                        ) -> std::fmt::Result {
+                            formatter.write_str("a string or an integer")
+                        }
+                        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            <$type>::from_str(v).map_err(E::custom)
+                        }
+                        // Formats such as TOML and YAML allow bare integers. They are parsed without a unit
+                        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            self.visit_str(&v.to_string())
+                        }
+                        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            self.visit_str(&v.to_string())
+                        }
+                    }
+                    // Formats that are not self describing such as postcard or bincode can not deserialize_any
+                    if deserializer.is_human_readable() {
+                        deserializer.deserialize_any(Visitor)
+                    } else {
+                        deserializer.deserialize_str(Visitor)
+                    }
                 }
             }
         };
     };
 }
-pub(crate) use serde_via_string_types;
+pub(crate) use serde_via_display;
 
 macro_rules! extend_string_from_and_to {
     ($type:ty, $error:ty) => {
@@ -55,3 +296,163 @@ macro_rules! extend_string_from_and_to {
     };
 }
 pub(crate) use extend_string_from_and_to;
+
+/// Deserializes an optional value that is parsed with [FromStr](std::str::FromStr). Treating an empty string as `None`
+///
+/// Integers are parsed the same as a string without a unit. Like [serde_via_display]
+pub(crate) fn deserialize_optional_from_str<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    struct Visitor<T>(std::marker::PhantomData<T>);
+    impl<'de, T> serde::de::Visitor<'de> for Visitor<T>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string, an integer or nothing")
+        }
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(None)
+        }
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(None)
+        }
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            // Formats that are not self describing such as postcard can not deserialize_any
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(self)
+            } else {
+                deserializer.deserialize_str(self)
+            }
+        }
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            if v.is_empty() {
+                return Ok(None);
+            }
+            T::from_str(v).map(Some).map_err(E::custom)
+        }
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            self.visit_str(&v.to_string())
+        }
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            self.visit_str(&v.to_string())
+        }
+    }
+    deserializer.deserialize_option(Visitor(std::marker::PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, str::FromStr};
+
+    use crate::size_config::{ConfigSize, InvalidSizeError};
+
+    /// A size that is 1MiB when the field is missing
+    #[derive(Debug, PartialEq)]
+    struct Retention(ConfigSize);
+    impl fmt::Display for Retention {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+    impl FromStr for Retention {
+        type Err = InvalidSizeError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            ConfigSize::from_str(s).map(Retention)
+        }
+    }
+    serde_via_string_types!(
+        Retention,
+        default = Retention(ConfigSize::new_from_mebibytes(1))
+    );
+
+    /// The same as [Retention] but also accepts a byte count
+    #[derive(Debug, PartialEq)]
+    struct Quota(ConfigSize);
+    impl fmt::Display for Quota {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+    impl FromStr for Quota {
+        type Err = InvalidSizeError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            ConfigSize::from_str(s).map(Quota)
+        }
+    }
+    serde_via_string_or_number_types!(
+        Quota,
+        |bytes| Quota(ConfigSize::from(
+            usize::try_from(bytes).unwrap_or(usize::MAX)
+        )),
+        default = Quota(ConfigSize::new_from_mebibytes(1))
+    );
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        retention: Retention,
+        quota: Quota,
+    }
+    #[test]
+    pub fn test_default() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            config.retention,
+            Retention(ConfigSize::new_from_mebibytes(1))
+        );
+        assert_eq!(config.quota, Quota(ConfigSize::new_from_mebibytes(1)));
+        let config: Config = serde_json::from_str(r#"{"retention": null, "quota": null}"#).unwrap();
+        assert_eq!(
+            config.retention,
+            Retention(ConfigSize::new_from_mebibytes(1))
+        );
+        assert_eq!(config.quota, Quota(ConfigSize::new_from_mebibytes(1)));
+
+        let config: Config = serde_json::from_str(r#"{"retention": "2KiB", "quota": 5}"#).unwrap();
+        assert_eq!(
+            config.retention,
+            Retention(ConfigSize::new_from_kibibytes(2))
+        );
+        assert_eq!(config.quota, Quota(ConfigSize::new_from_bytes(5)));
+        assert!(serde_json::from_str::<Config>(r#"{"retention": 5}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"quota": -5}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"quota": "1XiB"}"#).is_err());
+    }
+    #[test]
+    pub fn test_default_toml() {
+        let config: Config = toml::from_str(r#"quota = "1GiB""#).unwrap();
+        assert_eq!(
+            config.retention,
+            Retention(ConfigSize::new_from_mebibytes(1))
+        );
+        assert_eq!(config.quota, Quota(ConfigSize::from_gib(1)));
+    }
+}
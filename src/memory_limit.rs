@@ -0,0 +1,206 @@
+use serde::de::{Error as _, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::macros::extend_string_from_and_to;
+use crate::size_config::ConfigSize;
+
+type AnyError = Box<dyn Error + Send + Sync + 'static>;
+#[derive(Debug, Error)]
+#[error("{0}: {1:?}")]
+pub struct InvalidMemoryLimitError(&'static str, Option<AnyError>);
+
+impl From<(&'static str, AnyError)> for InvalidMemoryLimitError {
+    fn from(value: (&'static str, AnyError)) -> Self {
+        Self(value.0, Some(value.1))
+    }
+}
+impl From<&'static str> for InvalidMemoryLimitError {
+    fn from(value: &'static str) -> Self {
+        Self(value, None)
+    }
+}
+/// A memory limit with an optional soft limit (warn) and a hard limit (kill)
+///
+/// | Format          | Soft Limit | Hard Limit |
+/// |-----------------|------------|------------|
+/// | "256MiB"        | None       | 256MiB     |
+/// | "128MiB/256MiB" | 128MiB     | 256MiB     |
+///
+/// # Examples in TOML
+/// ```toml
+/// hard_only = "256MiB"
+/// soft_and_hard = "128MiB/256MiB"
+/// table = { soft = "128MiB", hard = "256MiB" }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConfigMemoryLimit {
+    soft: Option<ConfigSize>,
+    hard: ConfigSize,
+}
+impl ConfigMemoryLimit {
+    /// Creates a new memory limit. Errors if the soft limit is greater than the hard limit
+    pub fn new(
+        soft: Option<ConfigSize>,
+        hard: ConfigSize,
+    ) -> Result<Self, InvalidMemoryLimitError> {
+        if soft.is_some_and(|soft| soft > hard) {
+            return Err(InvalidMemoryLimitError::from(
+                "Soft limit is greater than the hard limit",
+            ));
+        }
+        Ok(Self { soft, hard })
+    }
+    pub fn soft(&self) -> Option<ConfigSize> {
+        self.soft
+    }
+    pub fn hard(&self) -> ConfigSize {
+        self.hard
+    }
+    /// If `current` is over the hard limit
+    pub fn is_exceeded(&self, current: ConfigSize) -> bool {
+        current > self.hard
+    }
+    /// If `current` is over the soft limit. Always false if there is no soft limit
+    pub fn is_soft_exceeded(&self, current: ConfigSize) -> bool {
+        self.soft.is_some_and(|soft| current > soft)
+    }
+}
+impl Display for ConfigMemoryLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.soft {
+            Some(soft) => write!(f, "{}/{}", soft, self.hard),
+            None => write!(f, "{}", self.hard),
+        }
+    }
+}
+impl FromStr for ConfigMemoryLimit {
+    type Err = InvalidMemoryLimitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            ConfigSize::from_str(value.trim())
+                .map_err(|v| InvalidMemoryLimitError::from(("Invalid Size", v.into())))
+        };
+        match s.split_once('/') {
+            Some((soft, hard)) => Self::new(Some(parse(soft)?), parse(hard)?),
+            None => Self::new(None, parse(s)?),
+        }
+    }
+}
+extend_string_from_and_to!(ConfigMemoryLimit, InvalidMemoryLimitError);
+
+impl Serialize for ConfigMemoryLimit {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for ConfigMemoryLimit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MemoryLimitVisitor;
+        impl<'de> Visitor<'de> for MemoryLimitVisitor {
+            type Value = ConfigMemoryLimit;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a memory limit string or a table with soft and hard limits")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                ConfigMemoryLimit::from_str(v).map_err(E::custom)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut soft = None;
+                let mut hard = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "soft" => soft = Some(map.next_value::<ConfigSize>()?),
+                        "hard" => hard = Some(map.next_value::<ConfigSize>()?),
+                        other => return Err(A::Error::unknown_field(other, &["soft", "hard"])),
+                    }
+                }
+                let hard = hard.ok_or_else(|| A::Error::missing_field("hard"))?;
+                ConfigMemoryLimit::new(soft, hard).map_err(A::Error::custom)
+            }
+        }
+        deserializer.deserialize_any(MemoryLimitVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{ONE_GIB, ONE_MIB};
+
+    #[derive(Serialize, Deserialize)]
+    pub struct SerdeTest {
+        pub limit: ConfigMemoryLimit,
+    }
+    #[test]
+    pub fn test_from_str() {
+        let limit = ConfigMemoryLimit::from_str("256MiB").unwrap();
+        assert_eq!(limit.soft(), None);
+        assert_eq!(limit.hard(), ConfigSize::new_from_mebibytes(256));
+        assert_eq!(limit.to_string(), "256MiB");
+
+        let limit = ConfigMemoryLimit::from_str("128MiB/256MiB").unwrap();
+        assert_eq!(limit.soft(), Some(ConfigSize::new_from_mebibytes(128)));
+        assert_eq!(limit.hard(), ConfigSize::new_from_mebibytes(256));
+        assert_eq!(limit.to_string(), "128MiB/256MiB");
+
+        assert!(ConfigMemoryLimit::from_str("256MiB/128MiB").is_err());
+    }
+    #[test]
+    pub fn test_exceeded() {
+        let limit = ConfigMemoryLimit::from_str("128MiB/256MiB").unwrap();
+        assert!(!limit.is_soft_exceeded(ConfigSize::new_from_mebibytes(128)));
+        assert!(limit.is_soft_exceeded(ConfigSize::new_from_mebibytes(129)));
+        assert!(!limit.is_exceeded(ConfigSize::new_from_mebibytes(256)));
+        assert!(limit.is_exceeded(ConfigSize::new_from_mebibytes(257)));
+
+        let limit = ConfigMemoryLimit::from_str("256MiB").unwrap();
+        assert!(!limit.is_soft_exceeded(ConfigSize::new_from_mebibytes(512)));
+
+        let limit = ConfigMemoryLimit::from_str("1MiB/1GiB").unwrap();
+        assert_eq!(limit.soft(), Some(ONE_MIB));
+        assert_eq!(limit.hard(), ONE_GIB);
+        assert!(!limit.is_soft_exceeded(ONE_MIB));
+        assert!(limit.is_soft_exceeded(ONE_GIB));
+        assert!(!limit.is_exceeded(ONE_GIB));
+    }
+    #[test]
+    pub fn test_serde() {
+        let test: SerdeTest = serde_json::from_str(r#"{"limit": "128MiB/256MiB"}"#).unwrap();
+        assert_eq!(test.limit.soft(), Some(ConfigSize::new_from_mebibytes(128)));
+        let table: SerdeTest =
+            serde_json::from_str(r#"{"limit": {"soft": "128MiB", "hard": "256MiB"}}"#).unwrap();
+        assert_eq!(test.limit, table.limit);
+        let hard_only: SerdeTest =
+            serde_json::from_str(r#"{"limit": {"hard": "256MiB"}}"#).unwrap();
+        assert_eq!(hard_only.limit.soft(), None);
+
+        assert!(serde_json::from_str::<SerdeTest>(r#"{"limit": {"soft": "1MiB"}}"#).is_err());
+        assert!(serde_json::from_str::<SerdeTest>(
+            r#"{"limit": {"soft": "512MiB", "hard": "256MiB"}}"#
+        )
+        .is_err());
+
+        let string = serde_json::to_string(&test).unwrap();
+        assert_eq!(string, r#"{"limit":"128MiB/256MiB"}"#);
+    }
+}
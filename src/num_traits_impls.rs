@@ -0,0 +1,103 @@
+//! [num-traits](https://docs.rs/num-traits) support for the config types
+//!
+//! `ConfigSize` and `ConfigDuration` implement [Zero], [CheckedAdd] and [CheckedSub] so they can be
+//! used with generic numeric code.
+//!
+//! `One` and `Num` are not implemented. Multiplying a size by a size does not produce a size.
+use num_traits::{CheckedAdd, CheckedSub, Zero};
+
+use crate::size_config::ConfigSize;
+
+impl Zero for ConfigSize {
+    fn zero() -> Self {
+        ConfigSize::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        ConfigSize::is_zero(self)
+    }
+}
+impl CheckedAdd for ConfigSize {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        ConfigSize::checked_add(*self, *v)
+    }
+}
+impl CheckedSub for ConfigSize {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        ConfigSize::checked_sub(*self, *v)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod duration {
+    use num_traits::{CheckedAdd, CheckedSub, Zero};
+
+    use crate::chrono_types::duration::ConfigDuration;
+
+    impl Zero for ConfigDuration {
+        fn zero() -> Self {
+            ConfigDuration::ZERO
+        }
+
+        fn is_zero(&self) -> bool {
+            ConfigDuration::is_zero(self)
+        }
+    }
+    impl CheckedAdd for ConfigDuration {
+        fn checked_add(&self, v: &Self) -> Option<Self> {
+            ConfigDuration::checked_add(*self, *v)
+        }
+    }
+    impl CheckedSub for ConfigDuration {
+        fn checked_sub(&self, v: &Self) -> Option<Self> {
+            ConfigDuration::checked_sub(*self, *v)
+        }
+    }
+}
+
+/// Sums the sizes. Returning `None` if the total overflows
+///
+/// ```rust
+/// use tuxs_config_types::{num_traits_impls::sum_sizes, size_config::ConfigSize};
+///
+/// let sizes = vec![ConfigSize::new_from_kibibytes(1), ConfigSize::new_from_bytes(1024)];
+/// assert_eq!(sum_sizes(sizes.into_iter()), Some(ConfigSize::new_from_kibibytes(2)));
+/// ```
+pub fn sum_sizes<I: Iterator<Item = ConfigSize>>(i: I) -> Option<ConfigSize> {
+    sum_checked(i)
+}
+
+fn sum_checked<T: Zero + CheckedAdd, I: Iterator<Item = T>>(mut i: I) -> Option<T> {
+    i.try_fold(T::zero(), |total, value| total.checked_add(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_sum_sizes() {
+        let sizes = vec![
+            ConfigSize::new_from_mebibytes(1),
+            ConfigSize::new_from_kibibytes(512),
+            ConfigSize::new_from_bytes(1),
+        ];
+        let total = sum_sizes(sizes.into_iter()).unwrap();
+        assert_eq!(total.get_as_bytes(), 1024 * 1024 + 512 * 1024 + 1);
+        assert_eq!(sum_sizes(std::iter::empty()), Some(ConfigSize::zero()));
+        assert_eq!(
+            sum_sizes([ConfigSize::MAX, ConfigSize::new_from_bytes(1)].into_iter()),
+            None
+        );
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_sum_durations() {
+        use crate::chrono_types::duration::ConfigDuration;
+        use std::str::FromStr;
+        let durations = ["1h", "30m", "15s"].map(|v| ConfigDuration::from_str(v).unwrap());
+        let total = sum_checked(durations.into_iter()).unwrap();
+        assert_eq!(total.num_seconds(), 5415);
+        assert!(ConfigDuration::zero().is_zero());
+    }
+}
@@ -0,0 +1,158 @@
+//! [postcard](https://docs.rs/postcard) helpers
+//!
+//! Unlike the other formats the values are not encoded as strings. Keeping the output small.
+//!
+//! - [ConfigSize] is encoded as a `(u64, u8)` tuple. The byte count and the index of the unit
+//! - [ConfigDuration](crate::chrono_types::duration::ConfigDuration) is encoded as an `i64` millisecond count
+//!
+//! Bit sizes that are not a whole number of bytes are rounded down to the nearest byte.
+use crate::size_config::{ConfigSize, Unit};
+
+impl ConfigSize {
+    /// Encodes the size as a `(u64, u8)` tuple. The byte count and the index of the unit
+    pub fn to_postcard(&self) -> Vec<u8> {
+        let unit = self.unit.index() as u8;
+        postcard::to_allocvec(&(self.into_bytes(), unit)).expect("Encoding into a Vec can not fail")
+    }
+    /// Decodes a size written by [ConfigSize::to_postcard]
+    ///
+    /// The unit is kept if the byte count is a whole number of it
+    pub fn from_postcard(bytes: &[u8]) -> Result<Self, postcard::Error> {
+        let (byte_count, unit): (u64, u8) = postcard::from_bytes(bytes)?;
+        let unit = Unit::from_index(unit as usize).ok_or(postcard::Error::SerdeDeCustom)?;
+        let byte_count = usize::try_from(byte_count).map_err(|_| postcard::Error::SerdeDeCustom)?;
+        let bits = byte_count as u128 * 8;
+        if bits % unit.bits() as u128 == 0 {
+            if let Ok(size) = usize::try_from(bits / unit.bits() as u128) {
+                return Ok(ConfigSize { size, unit });
+            }
+        }
+        Ok(ConfigSize::from(byte_count))
+    }
+}
+#[cfg(feature = "chrono")]
+impl crate::chrono_types::duration::ConfigDuration {
+    /// Encodes the duration as an `i64` millisecond count
+    pub fn to_postcard(&self) -> Vec<u8> {
+        postcard::to_allocvec(&self.num_milliseconds()).expect("Encoding into a Vec can not fail")
+    }
+    /// Decodes a duration written by [ConfigDuration::to_postcard](crate::chrono_types::duration::ConfigDuration::to_postcard)
+    pub fn from_postcard(bytes: &[u8]) -> Result<Self, postcard::Error> {
+        let milliseconds: i64 = postcard::from_bytes(bytes)?;
+        chrono::Duration::try_milliseconds(milliseconds)
+            .map(Self::from)
+            .ok_or(postcard::Error::SerdeDeCustom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_size() {
+        let size = ConfigSize::from_str("512MiB").unwrap();
+        let bytes = size.to_postcard();
+        assert_eq!(
+            bytes,
+            postcard::to_allocvec(&(512u64 * 1024 * 1024, 2u8)).unwrap()
+        );
+        assert_eq!(ConfigSize::from_postcard(&bytes).unwrap(), size);
+
+        let size = ConfigSize::from_str("16Kib").unwrap();
+        assert_eq!(
+            ConfigSize::from_postcard(&size.to_postcard()).unwrap(),
+            size
+        );
+        // Not a whole number of the unit. Falls back to the largest unit that fits
+        let bytes = postcard::to_allocvec(&(1536u64, 2u8)).unwrap();
+        assert_eq!(
+            ConfigSize::from_postcard(&bytes).unwrap(),
+            ConfigSize::new_from_bytes(1536)
+        );
+        let bytes = postcard::to_allocvec(&(1u64, u8::MAX)).unwrap();
+        assert!(ConfigSize::from_postcard(&bytes).is_err());
+        // Larger than any parsed size. The byte count saturates
+        let huge = ConfigSize::from_tib(usize::MAX);
+        assert_eq!(
+            ConfigSize::from_postcard(&huge.to_postcard()).unwrap(),
+            ConfigSize::from(usize::try_from(u64::MAX).unwrap_or(usize::MAX))
+        );
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        use crate::chrono_types::duration::ConfigDuration;
+        let duration = ConfigDuration::from_str("30s").unwrap();
+        let bytes = duration.to_postcard();
+        assert_eq!(bytes, postcard::to_allocvec(&30_000i64).unwrap());
+        assert_eq!(
+            ConfigDuration::from_postcard(&bytes)
+                .unwrap()
+                .num_milliseconds(),
+            30_000
+        );
+    }
+    #[test]
+    pub fn test_struct_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            cache: ConfigSize,
+            #[cfg(feature = "chrono")]
+            timeout: crate::chrono_types::duration::ConfigDuration,
+        }
+        let config = Config {
+            cache: ConfigSize::from_str("512MiB").unwrap(),
+            #[cfg(feature = "chrono")]
+            timeout: FromStr::from_str("30s").unwrap(),
+        };
+        let bytes = postcard::to_allocvec(&config).unwrap();
+        let decoded: Config = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, config);
+    }
+    #[test]
+    pub fn test_serde_as_unit() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::size_config::serde_as_mebibytes")]
+            cache: ConfigSize,
+        }
+        let config = Config {
+            cache: ConfigSize::new_from_mebibytes(512),
+        };
+        let bytes = postcard::to_allocvec(&config).unwrap();
+        assert_eq!(bytes, postcard::to_allocvec(&512u64).unwrap());
+        let decoded: Config = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, config);
+    }
+    #[test]
+    pub fn test_size_limit_round_trip() {
+        use crate::size_config::ConfigSizeLimit;
+        for limit in [
+            ConfigSizeLimit::Limited(ConfigSize::new_from_mebibytes(512)),
+            ConfigSizeLimit::Unlimited,
+        ] {
+            let bytes = postcard::to_allocvec(&limit).unwrap();
+            assert_eq!(bytes, postcard::to_allocvec(&limit.to_string()).unwrap());
+            assert_eq!(
+                postcard::from_bytes::<ConfigSizeLimit>(&bytes).unwrap(),
+                limit
+            );
+        }
+    }
+    #[test]
+    pub fn test_optional_size_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "crate::size_config::deserialize_optional_size")]
+            max_upload: Option<ConfigSize>,
+        }
+        for max_upload in [Some(ConfigSize::new_from_mebibytes(512)), None] {
+            let config = Config { max_upload };
+            let bytes = postcard::to_allocvec(&config).unwrap();
+            let decoded: Config = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, config);
+        }
+    }
+}
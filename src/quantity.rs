@@ -0,0 +1,117 @@
+//! Traits for writing code that works with any size or any duration
+//!
+//! ```rust
+//! use tuxs_config_types::quantity::ByteQuantity;
+//! use tuxs_config_types::size_config::ConfigSize;
+//! use std::str::FromStr;
+//!
+//! fn fits<T: ByteQuantity>(value: &T, budget: u64) -> bool {
+//!     value.total_bytes() <= budget as u128
+//! }
+//! assert!(fits(&ConfigSize::from_str("1KiB").unwrap(), 4096));
+//! assert!(!fits(&8192u64, 4096));
+//! ```
+
+/// A quantity of bytes
+pub trait ByteQuantity {
+    /// The total number of bytes. Rounded down to the whole byte
+    fn total_bytes(&self) -> u128;
+    /// If the two quantities have the same number of bytes. Regardless of their units
+    fn same_bytes<T: ByteQuantity + ?Sized>(&self, other: &T) -> bool {
+        self.total_bytes() == other.total_bytes()
+    }
+}
+/// A length of time
+pub trait TimeQuantity {
+    /// The total number of milliseconds. Truncated towards zero
+    fn total_millis(&self) -> i128;
+    /// If the two quantities have the same number of milliseconds. Regardless of their units
+    fn same_millis<T: TimeQuantity + ?Sized>(&self, other: &T) -> bool {
+        self.total_millis() == other.total_millis()
+    }
+}
+
+impl ByteQuantity for crate::size_config::ConfigSize {
+    fn total_bytes(&self) -> u128 {
+        u128::from(*self)
+    }
+}
+macro_rules! byte_quantity_for_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl ByteQuantity for $integer {
+                fn total_bytes(&self) -> u128 {
+                    *self as u128
+                }
+            }
+        )*
+    };
+}
+byte_quantity_for_integer!(u64, usize);
+
+impl TimeQuantity for std::time::Duration {
+    fn total_millis(&self) -> i128 {
+        self.as_millis() as i128
+    }
+}
+#[cfg(feature = "chrono")]
+impl TimeQuantity for chrono::Duration {
+    fn total_millis(&self) -> i128 {
+        self.num_milliseconds() as i128
+    }
+}
+#[cfg(feature = "chrono")]
+impl TimeQuantity for crate::chrono_types::duration::ConfigDuration {
+    fn total_millis(&self) -> i128 {
+        self.num_milliseconds() as i128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::size_config::ConfigSize;
+    use std::str::FromStr;
+
+    fn largest<T: ByteQuantity>(values: &[T]) -> u128 {
+        values
+            .iter()
+            .map(ByteQuantity::total_bytes)
+            .max()
+            .unwrap_or(0)
+    }
+    #[test]
+    pub fn test_byte_quantity() {
+        let sizes = [
+            ConfigSize::from_str("1KiB").unwrap(),
+            ConfigSize::from_str("2MiB").unwrap(),
+            ConfigSize::from_str("12b").unwrap(),
+        ];
+        assert_eq!(largest(&sizes), 2 * 1024 * 1024);
+        assert_eq!(largest(&[10u64, 20u64]), 20);
+        assert!(ConfigSize::from_str("1KiB").unwrap().same_bytes(&1024usize));
+        assert!(!ConfigSize::from_str("1KiB").unwrap().same_bytes(&1000u64));
+    }
+    #[test]
+    pub fn test_time_quantity() {
+        fn total<T: TimeQuantity>(values: &[T]) -> i128 {
+            values.iter().map(TimeQuantity::total_millis).sum()
+        }
+        let durations = [
+            std::time::Duration::from_secs(1),
+            std::time::Duration::from_millis(500),
+        ];
+        assert_eq!(total(&durations), 1500);
+        #[cfg(feature = "chrono")]
+        {
+            use crate::chrono_types::duration::ConfigDuration;
+            let durations = [
+                ConfigDuration::from_str("1m").unwrap(),
+                ConfigDuration::from_str("30s").unwrap(),
+            ];
+            assert_eq!(total(&durations), 90_000);
+            assert!(durations[1].same_millis(&std::time::Duration::from_secs(30)));
+            assert!(durations[0].same_millis(&chrono::Duration::seconds(60)));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! [MessagePack](https://msgpack.org) helpers using [rmp-serde](https://docs.rs/rmp-serde)
+//!
+//! Values are encoded as their string form. The same as JSON. So they stay readable in the binary output.
+use crate::size_config::ConfigSize;
+
+impl ConfigSize {
+    /// Encodes the size as a MessagePack string
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        rmp_serde::to_vec(self).expect("Encoding a string into a Vec can not fail")
+    }
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+#[cfg(feature = "chrono")]
+impl crate::chrono_types::duration::ConfigDuration {
+    /// Encodes the duration as a MessagePack string
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        rmp_serde::to_vec(self).expect("Encoding a string into a Vec can not fail")
+    }
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_size() {
+        let size = ConfigSize::from_str("512MiB").unwrap();
+        let bytes = size.to_msgpack();
+        assert_eq!(bytes, rmp_serde::to_vec("512MiB").unwrap());
+        assert_eq!(ConfigSize::from_msgpack(&bytes).unwrap(), size);
+        assert!(ConfigSize::from_msgpack(&rmp_serde::to_vec("512XB").unwrap()).is_err());
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        use crate::chrono_types::duration::ConfigDuration;
+        let duration = ConfigDuration::from_str("30s").unwrap();
+        let bytes = duration.to_msgpack();
+        assert_eq!(bytes, rmp_serde::to_vec("30s").unwrap());
+        assert_eq!(ConfigDuration::from_msgpack(&bytes).unwrap(), duration);
+    }
+}
@@ -0,0 +1,214 @@
+//! [serde_with](https://docs.rs/serde_with) adapters for the config types
+//!
+//! | Adapter                                   | Rust Type                   | Wire Format               |
+//! |-------------------------------------------|-----------------------------|---------------------------|
+//! | `SerdeConfigSize`                         | `u64` or `usize` byte count | Size string. `"512MiB"`   |
+//! | `SizeAsBytes` or `SerdeConfigSizeAsBytes` | `ConfigSize`                | Integer byte count        |
+//! | `SerdeConfigDuration`                     | `chrono::Duration`          | Duration string. `"30s"`  |
+//! | `DurationAsMillis`                        | `ConfigDuration`            | Integer millisecond count |
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use serde_with::serde_as;
+//! use tuxs_config_types::serde_with::SerdeConfigSize;
+//!
+//! #[serde_as]
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde_as(as = "SerdeConfigSize")]
+//!     max_upload_bytes: u64,
+//! }
+//! let config: Config = serde_json::from_str(r#"{"max_upload_bytes": "1MiB"}"#).unwrap();
+//! assert_eq!(config.max_upload_bytes, 1024 * 1024);
+//! ```
+use ::serde_with::{DeserializeAs, SerializeAs};
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::size_config::{serialize_as_unit, ConfigSize, Unit};
+
+/// Stores a byte count as an integer while using the [ConfigSize] string on the wire
+pub struct SerdeConfigSize;
+/// Stores a [ConfigSize] while using the integer byte count on the wire
+///
+/// The same as [serde_as_bytes](crate::size_config::serde_as_bytes). Sizes that are not a whole number of bytes fail to serialize
+pub struct SerdeConfigSizeAsBytes;
+/// The same as [SerdeConfigSizeAsBytes]. Named to pair with [DurationAsMillis]
+pub type SizeAsBytes = SerdeConfigSizeAsBytes;
+/// Stores a `chrono::Duration` while using the [ConfigDuration](crate::chrono_types::duration::ConfigDuration) string on the wire
+#[cfg(feature = "chrono")]
+pub struct SerdeConfigDuration;
+/// Stores a [ConfigDuration](crate::chrono_types::duration::ConfigDuration) while using the integer millisecond count on the wire
+#[cfg(feature = "chrono")]
+pub struct DurationAsMillis;
+
+macro_rules! size_as_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl SerializeAs<$integer> for SerdeConfigSize {
+                fn serialize_as<S>(source: &$integer, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    let bytes = usize::try_from(*source).map_err(S::Error::custom)?;
+                    ConfigSize::from(bytes).serialize(serializer)
+                }
+            }
+            impl<'de> DeserializeAs<'de, $integer> for SerdeConfigSize {
+                fn deserialize_as<D>(deserializer: D) -> Result<$integer, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let size = ConfigSize::deserialize(deserializer)?;
+                    <$integer>::try_from(size.get_as_bytes()).map_err(D::Error::custom)
+                }
+            }
+        )*
+    };
+}
+size_as_integer!(u64, usize);
+
+impl SerializeAs<ConfigSize> for SerdeConfigSizeAsBytes {
+    fn serialize_as<S>(source: &ConfigSize, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_as_unit(source, Unit::Bytes, serializer)
+    }
+}
+impl<'de> DeserializeAs<'de, ConfigSize> for SerdeConfigSizeAsBytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<ConfigSize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = u64::deserialize(deserializer)?;
+        let bytes = usize::try_from(bytes).map_err(D::Error::custom)?;
+        Ok(ConfigSize::from(bytes))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod duration {
+    use ::serde_with::{DeserializeAs, SerializeAs};
+    use chrono::Duration;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{DurationAsMillis, SerdeConfigDuration};
+    use crate::chrono_types::duration::ConfigDuration;
+
+    impl SerializeAs<Duration> for SerdeConfigDuration {
+        fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            ConfigDuration::from(*source).serialize(serializer)
+        }
+    }
+    impl<'de> DeserializeAs<'de, Duration> for SerdeConfigDuration {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            ConfigDuration::deserialize(deserializer).map(ConfigDuration::into_inner)
+        }
+    }
+    impl SerializeAs<ConfigDuration> for DurationAsMillis {
+        fn serialize_as<S>(source: &ConfigDuration, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            source.num_milliseconds().serialize(serializer)
+        }
+    }
+    impl<'de> DeserializeAs<'de, ConfigDuration> for DurationAsMillis {
+        fn deserialize_as<D>(deserializer: D) -> Result<ConfigDuration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let milliseconds = i64::deserialize(deserializer)?;
+            let duration = Duration::try_milliseconds(milliseconds)
+                .ok_or_else(|| D::Error::custom("Duration is out of range"))?;
+            Ok(ConfigDuration::in_largest_exact_unit(duration))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde_with::serde_as;
+    use std::str::FromStr;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct SerdeTest {
+        #[serde_as(as = "SerdeConfigSize")]
+        pub bytes: u64,
+        #[serde_as(as = "SerdeConfigSizeAsBytes")]
+        pub size: ConfigSize,
+        #[serde_as(as = "Option<SizeAsBytes>")]
+        pub limit: Option<ConfigSize>,
+    }
+    #[test]
+    pub fn test_size() {
+        let test: SerdeTest =
+            serde_json::from_str(r#"{"bytes": "2KiB", "size": 1048576, "limit": 1024}"#).unwrap();
+        assert_eq!(test.bytes, 2048);
+        assert_eq!(test.size, ConfigSize::new_from_mebibytes(1));
+        assert_eq!(test.limit, Some(ConfigSize::new_from_kibibytes(1)));
+
+        let json = serde_json::to_string(&test).unwrap();
+        assert_eq!(json, r#"{"bytes":"2KiB","size":1048576,"limit":1024}"#);
+        assert_eq!(serde_json::from_str::<SerdeTest>(&json).unwrap(), test);
+    }
+    #[test]
+    pub fn test_size_as_bytes_not_whole() {
+        let test = SerdeTest {
+            bytes: 0,
+            size: ConfigSize::from_str("12b").unwrap(),
+            limit: None,
+        };
+        let err = serde_json::to_string(&test).unwrap_err();
+        assert!(err.to_string().contains("not a whole number of"), "{}", err);
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        pub struct DurationTest {
+            #[serde_as(as = "SerdeConfigDuration")]
+            pub timeout: chrono::Duration,
+        }
+        let test: DurationTest = serde_json::from_str(r#"{"timeout": "30s"}"#).unwrap();
+        assert_eq!(test.timeout, chrono::Duration::seconds(30));
+        let json = serde_json::to_string(&test).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DurationTest>(&json).unwrap().timeout,
+            test.timeout
+        );
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration_as_millis() {
+        use crate::chrono_types::duration::ConfigDuration;
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        pub struct DurationTest {
+            #[serde_as(as = "DurationAsMillis")]
+            pub timeout: ConfigDuration,
+            #[serde_as(as = "Option<DurationAsMillis>")]
+            pub delay: Option<ConfigDuration>,
+        }
+        let test: DurationTest =
+            serde_json::from_str(r#"{"timeout": 90000, "delay": -1500}"#).unwrap();
+        assert_eq!(test.timeout.to_string(), "90s");
+        assert_eq!(test.delay.unwrap().to_string(), "-1500ms");
+        let json = serde_json::to_string(&test).unwrap();
+        assert_eq!(json, r#"{"timeout":90000,"delay":-1500}"#);
+        assert!(serde_json::from_str::<DurationTest>(&format!(
+            r#"{{"timeout": {}, "delay": null}}"#,
+            i64::MIN
+        ))
+        .is_err());
+    }
+}
@@ -1,25 +1,38 @@
 use derive_more::derive::{AsRef, Deref, DerefMut, From, Into};
+#[cfg(feature = "regex")]
 use regex::Regex;
 use std::error::Error;
+use std::iter::Sum;
+use std::ops::{Add, Range, RangeInclusive, Sub};
+use std::path::Path;
 use std::str::FromStr;
+#[cfg(feature = "regex")]
 use std::sync::OnceLock;
 use std::{cmp::Ordering, fmt::Display};
 use strum::{
-    AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, IntoEnumIterator, IntoStaticStr,
+    AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumMessage, EnumString, IntoEnumIterator,
+    IntoStaticStr,
 };
 use thiserror::Error;
 
-use crate::macros::{extend_string_from_and_to, serde_via_string_types};
+use crate::config_unit::ConfigUnit;
+use crate::macros::{
+    extend_string_from_and_to, serde_via_display, serde_via_string_or_number_types,
+    serde_via_string_types,
+};
+#[cfg(feature = "regex")]
 static UNITS_REGEX: OnceLock<Regex> = OnceLock::new();
+/// Longer inputs are rejected before they are parsed. No valid size comes close
+pub const MAX_INPUT_LEN: usize = 64;
 type AnyError = Box<dyn Error + Send + Sync + 'static>;
+/// The byte ranges of the size and the optional unit
+type SizeParts = (Range<usize>, Option<Range<usize>>);
 #[derive(
     Debug,
     Clone,
     Copy,
     PartialEq,
     Eq,
-    PartialOrd,
-    Ord,
     Hash,
     Default,
     Display,
@@ -29,61 +42,305 @@ type AnyError = Box<dyn Error + Send + Sync + 'static>;
     IntoStaticStr,
     EnumIter,
     EnumIs,
+    EnumMessage,
 )]
 #[cfg_attr(feature = "digestible", derive(digestible::Digestible))]
-#[repr(usize)]
 #[non_exhaustive]
 pub enum Unit {
     #[default]
-    #[strum(serialize = "B")]
-    Bytes = 1,
-    #[strum(serialize = "KiB")]
-    Kibibytes = 1024,
-    #[strum(serialize = "MiB")]
-    Mebibytes = 1024 * 2,
+    #[strum(serialize = "B", message = "Byte: 8 bits")]
+    Bytes,
+    #[strum(serialize = "KiB", message = "Kibibyte: 1024 bytes")]
+    Kibibytes,
+    #[strum(serialize = "MiB", message = "Mebibyte: 1024 kibibytes")]
+    Mebibytes,
+    #[strum(serialize = "b", message = "Bit: 1/8 of a byte")]
+    Bits,
+    #[strum(serialize = "Kib", message = "Kibibit: 1024 bits")]
+    Kibibits,
+    #[strum(serialize = "Mib", message = "Mebibit: 1024 kibibits")]
+    Mebibits,
+    #[strum(serialize = "GiB", message = "Gibibyte: 1024 mebibytes")]
+    Gibibytes,
+    #[strum(serialize = "TiB", message = "Tebibyte: 1024 gibibytes")]
+    Tebibytes,
 }
 serde_via_string_types!(Unit);
 
 impl Unit {
-    pub fn build_regex() -> Regex {
-        Regex::new(&Self::create_regex_string())
-            .map_err(|err| {
-                format!(
-                    "Unable to Build Size Config Regex. Please Report: {:?}. \n{:?}",
-                    Self::create_regex_string(),
-                    err
-                )
-            })
-            .unwrap()
+    /// The number of bits in one of this unit
+    pub const fn bits(&self) -> u64 {
+        match self {
+            Unit::Bytes => 8,
+            Unit::Kibibytes => 8 * 1024,
+            Unit::Mebibytes => 8 * 1024 * 1024,
+            Unit::Bits => 1,
+            Unit::Kibibits => 1024,
+            Unit::Mebibits => 1024 * 1024,
+            Unit::Gibibytes => 8 * 1024 * 1024 * 1024,
+            Unit::Tebibytes => 8 * 1024 * 1024 * 1024 * 1024,
+        }
     }
-
-    fn create_regex_string() -> String {
-        let mut unit_options = String::with_capacity(Unit::iter().count() * 4); // Most Units are 3 characters long + 1 for the pipe
-        let mut iter = Unit::iter().peekable();
-        while let Some(value) = iter.next() {
-            unit_options.push_str(value.into());
-            if iter.peek().is_some() {
-                unit_options.push('|');
-            }
+    /// What the unit is. Such as `Kibibyte: 1024 bytes`
+    pub fn description(&self) -> &'static str {
+        self.get_message().expect("Every unit has a strum message")
+    }
+    /// The name used when parsing and displaying. Such as `KiB`
+    pub fn short_name(&self) -> &'static str {
+        self.into()
+    }
+    /// The singular long name of the unit. Such as `mebibyte`
+    pub const fn long_name(&self) -> &'static str {
+        match self {
+            Unit::Bytes => "byte",
+            Unit::Kibibytes => "kibibyte",
+            Unit::Mebibytes => "mebibyte",
+            Unit::Gibibytes => "gibibyte",
+            Unit::Tebibytes => "tebibyte",
+            Unit::Bits => "bit",
+            Unit::Kibibits => "kibibit",
+            Unit::Mebibits => "mebibit",
+        }
+    }
+    /// The plural long name of the unit. Such as `mebibytes`
+    pub const fn long_name_plural(&self) -> &'static str {
+        match self {
+            Unit::Bytes => "bytes",
+            Unit::Kibibytes => "kibibytes",
+            Unit::Mebibytes => "mebibytes",
+            Unit::Gibibytes => "gibibytes",
+            Unit::Tebibytes => "tebibytes",
+            Unit::Bits => "bits",
+            Unit::Kibibits => "kibibits",
+            Unit::Mebibits => "mebibits",
+        }
+    }
+    /// Finds the unit for a long name such as `bytes` or `Mebibyte`
+    ///
+    /// Case insensitive and either singular or plural. The reverse of [Unit::long_name] and [Unit::long_name_plural]
+    pub fn from_long_name(name: &str) -> Option<Unit> {
+        let name = name.to_ascii_lowercase();
+        let singular = name.strip_suffix('s').unwrap_or(&name);
+        match singular {
+            "byte" => Some(Unit::Bytes),
+            "kibibyte" => Some(Unit::Kibibytes),
+            "mebibyte" => Some(Unit::Mebibytes),
+            "gibibyte" => Some(Unit::Gibibytes),
+            "tebibyte" => Some(Unit::Tebibytes),
+            "bit" => Some(Unit::Bits),
+            "kibibit" => Some(Unit::Kibibits),
+            "mebibit" => Some(Unit::Mebibits),
+            _ => None,
         }
-        format!(r#"(?<size>[0-9]+)(?<unit>[{}]+)?"#, unit_options)
+    }
+    /// The number of units. The same as [EnumCount::COUNT]
+    pub const fn count() -> usize {
+        Self::COUNT
+    }
+    /// Every unit in declaration order. The same order as [Unit::iter]
+    pub const fn all() -> &'static [Unit] {
+        &[
+            Unit::Bytes,
+            Unit::Kibibytes,
+            Unit::Mebibytes,
+            Unit::Bits,
+            Unit::Kibibits,
+            Unit::Mebibits,
+            Unit::Gibibytes,
+            Unit::Tebibytes,
+        ]
+    }
+    /// The position of the unit in [Unit::all]. Such as `1 for Kibibytes`
+    pub fn index(self) -> usize {
+        Self::all()
+            .iter()
+            .position(|unit| *unit == self)
+            .expect("Every unit is in Unit::all")
+    }
+    /// The unit at `index` in [Unit::all]. `None` if out of range
+    pub fn from_index(index: usize) -> Option<Unit> {
+        Self::all().get(index).copied()
+    }
+    /// If this unit counts bits instead of bytes
+    pub const fn is_bit_unit(&self) -> bool {
+        matches!(self, Unit::Bits | Unit::Kibibits | Unit::Mebibits)
+    }
+    /// The next larger unit of the same kind. Byte units never step into bit units
+    ///
+    /// Returns `None` for the largest unit
+    pub fn next_larger(&self) -> Option<Unit> {
+        let mut units = Unit::iter().filter(|unit| unit.is_bit_unit() == self.is_bit_unit());
+        units.find(|unit| unit == self)?;
+        units.next()
+    }
+    /// The next smaller unit of the same kind. Byte units never step into bit units
+    ///
+    /// Returns `None` for the smallest unit
+    pub fn next_smaller(&self) -> Option<Unit> {
+        Unit::iter()
+            .filter(|unit| unit.is_bit_unit() == self.is_bit_unit())
+            .take_while(|unit| unit != self)
+            .last()
+    }
+}
+/// Units are ordered by their size in bits. Not by declaration order
+impl PartialOrd for Unit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Unit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bits().cmp(&other.bits())
+    }
+}
+impl ConfigUnit for Unit {
+    #[cfg(feature = "regex")]
+    fn create_regex_string() -> String {
+        format!(
+            r#"^(?<size>[0-9]+)(?:(?<unit>{})|(?<invalid>.+))?$"#,
+            Self::unit_options()
+        )
     }
 }
+/// Finds the byte unit with the multiplier. Such as `1024` for [Unit::Kibibytes]
+///
+/// Bit units are never returned
+impl TryFrom<usize> for Unit {
+    type Error = InvalidSizeError;
+
+    fn try_from(multiplier: usize) -> Result<Self, Self::Error> {
+        Unit::iter()
+            .filter(|unit| !unit.is_bit_unit())
+            .find(|unit| unit.bits() / 8 == multiplier as u64)
+            .ok_or(InvalidSizeError::from("Not the multiplier of a unit"))
+    }
+}
+/// Use the accessors such as [InvalidSizeError::context] and [InvalidSizeError::span] to inspect the error.
+/// They are the stable interface. The fields are private and may change
 #[derive(Debug, Error)]
 #[error("{0}: {1:?}")]
-pub struct InvalidSizeError(&'static str, Option<AnyError>);
+#[non_exhaustive]
+pub struct InvalidSizeError(
+    &'static str,
+    Option<AnyError>,
+    Option<Range<usize>>,
+    Option<String>,
+);
 
 impl From<(&'static str, AnyError)> for InvalidSizeError {
     fn from(value: (&'static str, AnyError)) -> Self {
-        Self(value.0, Some(value.1))
+        Self(value.0, Some(value.1), None, None)
     }
 }
 impl From<&'static str> for InvalidSizeError {
     fn from(value: &'static str) -> Self {
-        Self(value, None)
+        Self(value, None, None, None)
+    }
+}
+impl InvalidSizeError {
+    /// What went wrong. Such as `Unknown unit`
+    pub fn context(&self) -> &str {
+        self.0
+    }
+    /// The underlying error. Such as the integer parse error for a number that is too large
+    pub fn source_error(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.1.as_deref()
+    }
+    /// The byte range of the input that caused the error
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.2.clone()
+    }
+    /// The string that failed to parse. Only set by [FromStr] and only for inputs up to [MAX_INPUT_LEN] bytes
+    pub fn input(&self) -> Option<&str> {
+        self.3.as_deref()
+    }
+    pub(crate) fn with_span(mut self, span: Range<usize>) -> Self {
+        self.2 = Some(span);
+        self
+    }
+    /// Removes the span. For errors from a rewritten input where the span would point to the wrong place
+    pub(crate) fn without_span(mut self) -> Self {
+        self.2 = None;
+        self
+    }
+    pub(crate) fn with_input(mut self, input: &str) -> Self {
+        if input.len() <= MAX_INPUT_LEN {
+            self.3 = Some(input.to_owned());
+        }
+        self
+    }
+}
+/// Returned by [ConfigSize::read_from_file]
+#[derive(Debug, Error)]
+pub enum ConfigSizeFileError {
+    #[error("Unable to read the size file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid size in file: {0}")]
+    InvalidSize(#[from] InvalidSizeError),
+}
+/// Which unit names are accepted when parsing a [ConfigSize]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AliasMode {
+    /// Only the canonical unit names. Such as `MiB`
+    #[default]
+    Strict,
+    /// Also accepts `K`, `M`, `G` and `T` with or without a trailing `B`. Such as `512MB` or `5k`
+    ///
+    /// # **The aliases are binary units. `512MB` is 512 × 1,048,576 bytes. Not 512 × 1,000,000 bytes**
+    ///
+    /// This matches how tools such as Docker read these suffixes. The letter is case insensitive.
+    /// A trailing lower case `b` is still rejected because it means bits. A space is allowed before the unit
+    Permissive,
+}
+impl AliasMode {
+    fn resolve(&self, unit: &str) -> Option<Unit> {
+        match self {
+            AliasMode::Strict => None,
+            AliasMode::Permissive => {
+                let prefix = unit.strip_suffix('B').unwrap_or(unit);
+                match prefix.to_ascii_uppercase().as_str() {
+                    "K" => Some(Unit::Kibibytes),
+                    "M" => Some(Unit::Mebibytes),
+                    "G" => Some(Unit::Gibibytes),
+                    "T" => Some(Unit::Tebibytes),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+/// Number conventions used by [ConfigSize::from_str_with_locale]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SizeLocale {
+    /// `1,024.5`
+    #[default]
+    En,
+    /// `1.024,5`
+    De,
+    /// `1 024,5`. Also accepts a no-break space or narrow no-break space as the separator
+    Fr,
+}
+impl SizeLocale {
+    fn is_thousands_separator(&self, c: char) -> bool {
+        match self {
+            SizeLocale::En => c == ',',
+            SizeLocale::De => c == '.',
+            SizeLocale::Fr => matches!(c, ' ' | '\u{a0}' | '\u{202f}'),
+        }
+    }
+    fn decimal_separator(&self) -> char {
+        match self {
+            SizeLocale::En => '.',
+            SizeLocale::De | SizeLocale::Fr => ',',
+        }
     }
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, From, AsRef, Deref, DerefMut, Into)]
+/// A size such as `100MiB` or `10B`
+///
+/// Equality, ordering and hashing compare the total number of bits. So `1024KiB` equals `1MiB`.
+/// With the `digestible` feature the digest includes the unit. So `1024KiB` and `1MiB` produce different digests
+#[derive(Debug, Clone, Copy, From, AsRef, Deref, DerefMut, Into)]
 #[cfg_attr(feature = "digestible", derive(digestible::Digestible))]
 pub struct ConfigSize {
     #[deref]
@@ -91,10 +348,39 @@ pub struct ConfigSize {
     pub size: usize,
     pub unit: Unit,
 }
-serde_via_string_types!(ConfigSize);
+serde_via_display!(ConfigSize);
+/// Zero is always written as `0B`. Whatever unit it is stored in
+///
+/// The alternate form `{:#}` uses the long unit name. Such as `1 mebibyte` or `2 mebibytes`
 impl Display for ConfigSize {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.size, self.unit)
+        let (size, unit) = if self.is_zero() {
+            (0, Unit::Bytes)
+        } else {
+            self.as_parts()
+        };
+        if f.alternate() {
+            let name = if size == 1 {
+                unit.long_name()
+            } else {
+                unit.long_name_plural()
+            };
+            write!(f, "{} {}", size, name)
+        } else {
+            write!(f, "{}{}", size, unit)
+        }
+    }
+}
+/// The number of bytes in hex. Bit units are rounded down to whole bytes
+impl std::fmt::LowerHex for ConfigSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&(self.total_bits() / 8), f)
+    }
+}
+/// The number of bytes in binary. Bit units are rounded down to whole bytes
+impl std::fmt::Binary for ConfigSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&(self.total_bits() / 8), f)
     }
 }
 
@@ -102,62 +388,387 @@ impl FromStr for ConfigSize {
     type Err = InvalidSizeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let regex = UNITS_REGEX.get_or_init(Unit::build_regex);
-        let captures = regex
-            .captures(s)
-            .ok_or_else(|| InvalidSizeError::from("Does not meet requirements for a size"))?;
-        let size = captures
-            .name("size")
-            .unwrap()
-            .as_str()
+        Self::parse_with_options(s, false).map_err(|err| err.with_input(s))
+    }
+}
+extend_string_from_and_to!(ConfigSize, InvalidSizeError);
+impl ConfigSize {
+    /// Parses a size. The same as [FromStr::from_str] without needing the trait in scope
+    pub fn parse(s: &str) -> Result<Self, InvalidSizeError> {
+        Self::from_str(s)
+    }
+    /// Reads a size from a text file. Such as a cgroup memory limit
+    ///
+    /// Whitespace around the size is trimmed. So a trailing new line is fine
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<ConfigSize, ConfigSizeFileError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(Self::from_str(contents.trim())?)
+    }
+    /// Parses a size. Returning `default` if it does not parse
+    pub fn parse_or(s: &str, default: ConfigSize) -> ConfigSize {
+        Self::from_str(s).unwrap_or(default)
+    }
+    /// The same as [ConfigSize::parse_or] but logs a warning with the error when falling back
+    #[cfg(feature = "log")]
+    pub fn parse_or_warn(s: &str, default: ConfigSize) -> ConfigSize {
+        Self::from_str(s).unwrap_or_else(|err| {
+            log::warn!("Invalid size {:?}. Using {} instead: {}", s, default, err);
+            default
+        })
+    }
+    /// Parses a size like [FromStr] but rejects a size with leading zeros. Such as `007MiB`
+    ///
+    /// A lone `0` is still accepted
+    pub fn from_str_strict(s: &str) -> Result<Self, InvalidSizeError> {
+        Self::parse_with_options(s, true).map_err(|err| err.with_input(s))
+    }
+
+    /// Parses a size accepting the [AliasMode::Permissive] unit aliases
+    ///
+    /// # **`MB` means 1,048,576 bytes here. Not 1,000,000 bytes**
+    ///
+    /// ```rust
+    /// use tuxs_config_types::size_config::ConfigSize;
+    /// let size = ConfigSize::from_str_permissive("512MB").unwrap();
+    /// assert_eq!(size.get_as_bytes(), 512 * 1_048_576);
+    /// ```
+    pub fn from_str_permissive(s: &str) -> Result<Self, InvalidSizeError> {
+        Self::from_str_with_alias_mode(s, AliasMode::Permissive)
+    }
+    /// Parses a size accepting the unit aliases of `mode`
+    pub fn from_str_with_alias_mode(s: &str, mode: AliasMode) -> Result<Self, InvalidSizeError> {
+        if mode == AliasMode::Strict {
+            return Self::from_str(s);
+        }
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        let unit = s[digits..].trim_start();
+        if digits == 0 || unit.is_empty() {
+            return Self::from_str(s);
+        }
+        let unit = mode.resolve(unit).map_or(unit, |unit| unit.short_name());
+        Self::parse_with_unit(s, digits, unit)
+    }
+    /// Parses a size also accepting long unit names. Such as `10 bytes` or `5 mebibytes`
+    ///
+    /// The long names are case insensitive and can be singular or plural. A space is allowed before any unit.
+    /// Such as `5 MiB`. Binary prefixes missing the `B` such as `100Ki` or `100Mi` are read as the byte unit.
+    ///
+    /// Ambiguous spellings such as `kb` are still rejected. [Display] always writes the short unit
+    pub fn from_str_lenient(s: &str) -> Result<Self, InvalidSizeError> {
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        let unit = s[digits..].trim_start();
+        if digits == 0 || unit.is_empty() {
+            return Self::from_str(s);
+        }
+        let long_unit = Unit::from_long_name(unit).or_else(|| {
+            unit.ends_with('i')
+                .then(|| Unit::from_str(&format!("{}B", unit)).ok())
+                .flatten()
+        });
+        Self::parse_with_unit(s, digits, long_unit.map_or(unit, |unit| unit.short_name()))
+    }
+    /// Parses the digits of `s` followed by `unit`. Used when the unit was written differently in `s`
+    ///
+    /// Error spans point into `s`. A span in the unit covers everything after the space following the digits
+    fn parse_with_unit(s: &str, digits: usize, unit: &str) -> Result<Self, InvalidSizeError> {
+        let unit_start = s.len() - s[digits..].trim_start().len();
+        Self::parse_with_options(&format!("{}{}", &s[..digits], unit), false).map_err(|mut err| {
+            err.2 = err.2.map(|span| match span.start {
+                0 if span.end > digits => 0..s.len(),
+                start if start >= digits => unit_start..s.len(),
+                _ => span,
+            });
+            err.with_input(s)
+        })
+    }
+    /// Parses a size written with the number conventions of a locale. Such as `1,024 MiB` or `1.024 MiB`
+    ///
+    /// Thousands separators must group exactly three digits and a space is allowed before the unit.
+    /// Sizes are whole numbers so a decimal separator is an error. As is a thousands separator from another locale.
+    /// Such as `1 024 MiB` with [SizeLocale::En]. Errors do not include a span
+    pub fn from_str_with_locale(s: &str, locale: SizeLocale) -> Result<Self, InvalidSizeError> {
+        let input = s;
+        let s = s.trim();
+        let number_end = s
+            .find(|c: char| {
+                !(c.is_ascii_digit()
+                    || locale.is_thousands_separator(c)
+                    || c == locale.decimal_separator())
+            })
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(number_end);
+        let number = number.trim_end();
+        if number.contains(locale.decimal_separator()) {
+            return Err(InvalidSizeError::from("Sizes must be whole numbers").with_input(input));
+        }
+        // A separator this locale does not use stops the number early. Leaving digits before the unit
+        let mut after_number = unit.chars();
+        if let (Some(separator), Some(next)) = (after_number.next(), after_number.next()) {
+            if !separator.is_alphanumeric() && next.is_ascii_digit() {
+                return Err(InvalidSizeError::from(
+                    "Thousands separator does not match the locale",
+                )
+                .with_input(input));
+            }
+        }
+        let groups: Vec<&str> = number
+            .split(|c: char| locale.is_thousands_separator(c))
+            .collect();
+        if let [first, rest @ ..] = groups.as_slice() {
+            let valid_grouping = rest.is_empty()
+                || ((1..=3).contains(&first.len()) && rest.iter().all(|group| group.len() == 3));
+            if !valid_grouping {
+                return Err(InvalidSizeError::from("Invalid thousands grouping").with_input(input));
+            }
+        }
+        let canonical = format!("{}{}", groups.concat(), unit.trim_start());
+        Self::from_str(&canonical).map_err(|err| err.without_span().with_input(input))
+    }
+
+    /// [FromStr] using the hand written parser of the `fast-parse` feature. Whether or not the feature is enabled
+    ///
+    /// Only for comparing the parsers in one benchmark. Use [FromStr]
+    #[doc(hidden)]
+    pub fn from_str_by_hand(s: &str) -> Result<Self, InvalidSizeError> {
+        Self::parse_split(s, false, Self::split_by_hand).map_err(|err| err.with_input(s))
+    }
+    /// [FromStr] using the regex parser. Whether or not `fast-parse` is enabled
+    ///
+    /// Only for comparing the parsers in one benchmark. Use [FromStr]
+    #[doc(hidden)]
+    #[cfg(feature = "regex")]
+    pub fn from_str_with_regex(s: &str) -> Result<Self, InvalidSizeError> {
+        Self::parse_split(s, false, Self::split_with_regex).map_err(|err| err.with_input(s))
+    }
+    fn parse_with_options(s: &str, strict: bool) -> Result<Self, InvalidSizeError> {
+        #[cfg(all(feature = "regex", not(feature = "fast-parse")))]
+        let split = Self::split_with_regex;
+        #[cfg(any(not(feature = "regex"), feature = "fast-parse"))]
+        let split = Self::split_by_hand;
+        Self::parse_split(s, strict, split)
+    }
+    fn parse_split(
+        s: &str,
+        strict: bool,
+        split: fn(&str) -> Result<SizeParts, InvalidSizeError>,
+    ) -> Result<Self, InvalidSizeError> {
+        if s.len() > MAX_INPUT_LEN {
+            return Err(InvalidSizeError::from("Input is too long").with_span(0..s.len()));
+        }
+        let (size_span, unit_span) = split(s)?;
+        let size = &s[size_span.clone()];
+        if strict && size.len() > 1 && size.starts_with('0') {
+            return Err(
+                InvalidSizeError::from("Leading zeros are not allowed").with_span(size_span)
+            );
+        }
+        let size = size
             .parse::<usize>()
-            .map_err(|v| InvalidSizeError::from(("Invalid Size", v.into())))?;
+            .map_err(|v| InvalidSizeError::from(("Invalid Size", v.into())).with_span(size_span))?;
 
-        let unit = captures
-            .name("unit")
-            .map(|unit| {
-                Unit::from_str(unit.as_str())
-                    .map_err(|v| InvalidSizeError::from(("Invalid Size", v.into())))
+        let unit = unit_span
+            .map(|unit_span| {
+                Unit::from_str(&s[unit_span.clone()]).map_err(|v| {
+                    InvalidSizeError::from(("Invalid Size", v.into())).with_span(unit_span)
+                })
             })
             .transpose()?
             .unwrap_or_default();
 
-        Ok(Self { size, unit })
+        let config_size = Self { size, unit };
+        if !config_size.fits_in_usize() {
+            return Err(InvalidSizeError::from("Size is too large").with_span(0..s.len()));
+        }
+        Ok(config_size)
+    }
+    /// Finds the size and unit parts of the input
+    #[cfg(feature = "regex")]
+    fn split_with_regex(s: &str) -> Result<SizeParts, InvalidSizeError> {
+        let regex = UNITS_REGEX.get_or_init(Unit::build_regex);
+        let captures = regex.captures(s).ok_or_else(|| {
+            InvalidSizeError::from("Does not meet requirements for a size").with_span(0..s.len())
+        })?;
+        if let Some(invalid) = captures.name("invalid") {
+            return Err(InvalidSizeError::from("Unknown unit").with_span(invalid.range()));
+        }
+        let size = captures.name("size").unwrap().range();
+        Ok((size, captures.name("unit").map(|unit| unit.range())))
+    }
+    /// Finds the size and unit parts of the input without building the regex
+    ///
+    /// Accepts exactly the same inputs as the regex
+    fn split_by_hand(s: &str) -> Result<SizeParts, InvalidSizeError> {
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        // The regex does not match line breaks in the unit
+        if digits == 0 || s.contains('\n') {
+            return Err(
+                InvalidSizeError::from("Does not meet requirements for a size")
+                    .with_span(0..s.len()),
+            );
+        }
+        let rest = &s[digits..];
+        if rest.is_empty() {
+            return Ok((0..digits, None));
+        }
+        if Unit::iter().any(|unit| unit.as_ref() == rest) {
+            Ok((0..digits, Some(digits..s.len())))
+        } else {
+            Err(InvalidSizeError::from("Unknown unit").with_span(digits..s.len()))
+        }
     }
 }
-extend_string_from_and_to!(ConfigSize, InvalidSizeError);
 impl From<usize> for ConfigSize {
     fn from(value: usize) -> Self {
-        if value % (Unit::Mebibytes as usize) == 0 {
-            Self::new_from_mebibytes(value / (Unit::Mebibytes as usize))
-        } else if value % (Unit::Kibibytes as usize) == 0 {
-            Self::new_from_kibibytes(value / (Unit::Kibibytes as usize))
+        const MEBIBYTE: usize = (Unit::Mebibytes.bits() / 8) as usize;
+        const KIBIBYTE: usize = (Unit::Kibibytes.bits() / 8) as usize;
+        if value % MEBIBYTE == 0 {
+            Self::new_from_mebibytes(value / MEBIBYTE)
+        } else if value % KIBIBYTE == 0 {
+            Self::new_from_kibibytes(value / KIBIBYTE)
         } else {
             Self::new_from_bytes(value)
         }
     }
 }
-impl From<ConfigSize> for usize {
+/// The number of bytes. Bit sizes are rounded down to the whole byte
+///
+/// Errors if the byte count does not fit. Which can happen with large units on 32 bit targets.
+/// This replaces the old `From<ConfigSize> for usize`. Use `u128::from` for a conversion that can not fail
+impl TryFrom<ConfigSize> for usize {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(val: ConfigSize) -> Result<Self, Self::Error> {
+        usize::try_from(val.total_bits() / 8)
+    }
+}
+/// The number of bytes. Bit sizes are rounded down to the whole byte
+///
+/// Errors if the byte count does not fit. Such as `usize::MAX` TiB
+impl TryFrom<ConfigSize> for u64 {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(val: ConfigSize) -> Result<Self, Self::Error> {
+        u64::try_from(val.total_bits() / 8)
+    }
+}
+/// The number of bytes. Bit sizes are rounded down to the whole byte
+///
+/// Every size fits
+impl From<ConfigSize> for u128 {
+    fn from(val: ConfigSize) -> Self {
+        val.total_bits() / 8
+    }
+}
+/// The number of bytes. Bit sizes can have a fractional part
+impl From<ConfigSize> for f64 {
     fn from(val: ConfigSize) -> Self {
-        val.size * (val.unit as usize)
+        val.total_bits() as f64 / 8.0
     }
 }
 
+impl PartialEq for ConfigSize {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_bits() == other.total_bits()
+    }
+}
+impl Eq for ConfigSize {}
+/// Parses the string and compares the total number of bits. `false` if it does not parse
+impl PartialEq<str> for ConfigSize {
+    fn eq(&self, other: &str) -> bool {
+        ConfigSize::from_str(other).is_ok_and(|other| *self == other)
+    }
+}
+impl PartialEq<&str> for ConfigSize {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+impl std::hash::Hash for ConfigSize {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.total_bits().hash(state);
+    }
+}
 impl PartialOrd for ConfigSize {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.get_as_bytes().cmp(&other.get_as_bytes()))
+        Some(self.cmp(other))
     }
 }
 impl Ord for ConfigSize {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.get_as_bytes().cmp(&other.get_as_bytes())
+        self.total_bits().cmp(&other.total_bits())
+    }
+}
+/// Compares a [ConfigSize] with a raw byte count
+macro_rules! compare_with_bytes {
+    ($($integer:ty),*) => {
+        $(
+            impl PartialEq<$integer> for ConfigSize {
+                fn eq(&self, other: &$integer) -> bool {
+                    self.total_bits() == *other as u128 * 8
+                }
+            }
+            impl PartialEq<ConfigSize> for $integer {
+                fn eq(&self, other: &ConfigSize) -> bool {
+                    other == self
+                }
+            }
+            impl PartialOrd<$integer> for ConfigSize {
+                fn partial_cmp(&self, other: &$integer) -> Option<Ordering> {
+                    Some(self.total_bits().cmp(&(*other as u128 * 8)))
+                }
+            }
+            impl PartialOrd<ConfigSize> for $integer {
+                fn partial_cmp(&self, other: &ConfigSize) -> Option<Ordering> {
+                    other.partial_cmp(self).map(Ordering::reverse)
+                }
+            }
+        )*
+    };
+}
+compare_with_bytes!(u64, usize);
+impl Add for ConfigSize {
+    type Output = ConfigSize;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("overflow when adding sizes")
+    }
+}
+impl Sub for ConfigSize {
+    type Output = ConfigSize;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting sizes")
+    }
+}
+impl Sum for ConfigSize {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Add::add).unwrap_or(ConfigSize::ZERO)
+    }
+}
+impl<'a> Sum<&'a ConfigSize> for ConfigSize {
+    fn sum<I: Iterator<Item = &'a ConfigSize>>(iter: I) -> Self {
+        iter.copied().sum()
     }
 }
 impl ConfigSize {
+    /// A size of zero bytes
+    pub const ZERO: ConfigSize = ConfigSize {
+        size: 0,
+        unit: Unit::Bytes,
+    };
+    /// The largest byte total a `ConfigSize` can represent
+    ///
+    /// Parsing and [ConfigSizeBuilder::build] reject anything larger. Such as `16777216TiB` on 64 bit targets
+    pub const MAX: ConfigSize = ConfigSize {
+        size: usize::MAX,
+        unit: Unit::Bytes,
+    };
+
     pub fn new_from_bytes(size: usize) -> Self {
         Self {
             size,
-            unit: Unit::Kibibytes,
+            unit: Unit::Bytes,
         }
     }
     pub fn new_from_kibibytes(size: usize) -> Self {
@@ -172,11 +783,598 @@ impl ConfigSize {
             unit: Unit::Mebibytes,
         }
     }
+    /// Short for [ConfigSize::new_from_bytes]
+    pub fn from_b(size: usize) -> Self {
+        Self::new_from_bytes(size)
+    }
+    /// Short for [ConfigSize::new_from_kibibytes]
+    pub fn from_kib(size: usize) -> Self {
+        Self::new_from_kibibytes(size)
+    }
+    /// Short for [ConfigSize::new_from_mebibytes]
+    pub fn from_mib(size: usize) -> Self {
+        Self::new_from_mebibytes(size)
+    }
+    /// A size in [Unit::Gibibytes]. There is no long form constructor for it
+    pub fn from_gib(size: usize) -> Self {
+        Self {
+            size,
+            unit: Unit::Gibibytes,
+        }
+    }
+    /// A size in [Unit::Tebibytes]. There is no long form constructor for it
+    pub fn from_tib(size: usize) -> Self {
+        Self {
+            size,
+            unit: Unit::Tebibytes,
+        }
+    }
+    /// The total number of bytes. Saturating at `usize::MAX`
+    ///
+    /// Sizes in bits are rounded down to the whole byte. Parsed sizes always fit.
+    /// Only a size built directly such as `ConfigSize::from_tib(usize::MAX)` can saturate
     pub fn get_as_bytes(&self) -> usize {
-        self.size * (self.unit as usize)
+        usize::try_from(*self).unwrap_or(usize::MAX)
+    }
+    /// Formats the size for people using the largest unit that fits. Such as `1.5 GiB` or `512 Bytes`
+    ///
+    /// Up to two decimal places are used when the size is not a whole number of the unit
+    pub fn human_readable(&self) -> String {
+        const UNITS: [&str; 7] = ["Bytes", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+        let bytes = u128::from(*self);
+        if bytes == 1 {
+            return "1 Byte".to_owned();
+        }
+        let mut index = (0..UNITS.len())
+            .rev()
+            .find(|index| bytes >> (10 * index) > 0)
+            .unwrap_or(0);
+        let mut value = bytes as f64 / (1u128 << (10 * index)) as f64;
+        // Rounding can carry over into the next unit. Such as 1023.999 KiB
+        if (value * 100.0).round() / 100.0 >= 1024.0 && index + 1 < UNITS.len() {
+            index += 1;
+            value /= 1024.0;
+        }
+        format!("{} {}", (value * 100.0).round() / 100.0, UNITS[index])
+    }
+    /// Formats the size with the digits grouped in threes. Such as `1,048,576B`
+    ///
+    /// [FromStr] does not accept the separators. Use [ConfigSize::from_str_with_locale] with [SizeLocale::En] to read it back
+    pub fn to_grouped_string(&self) -> String {
+        if self.is_zero() {
+            return "0B".to_owned();
+        }
+        let (size, unit) = self.as_parts();
+        let digits = size.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 3);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        grouped.push_str(unit.as_ref());
+        grouped
+    }
+    /// If the size is zero. In any unit
+    pub fn is_zero(&self) -> bool {
+        self.size == 0
+    }
+    /// `numerator / denominator` as a float. Such as `0.5` for `512MiB` of `1GiB`
+    ///
+    /// Follows float division. A zero denominator gives `f64::INFINITY`, or `NaN` if the numerator is also zero
+    pub fn ratio(numerator: ConfigSize, denominator: ConfigSize) -> f64 {
+        f64::from(numerator) / f64::from(denominator)
+    }
+    /// How much of `total` this size is as a percentage. Such as `50.0` for `512MiB` of `1GiB`
+    ///
+    /// A zero `total` gives `0.0`. Unlike [ConfigSize::ratio] which follows float division.
+    /// So an empty quota shows as 0% used instead of infinity or `NaN`
+    pub fn percent_of(&self, total: &ConfigSize) -> f64 {
+        if total.is_zero() {
+            return 0.0;
+        }
+        Self::ratio(*self, *total) * 100.0
+    }
+    /// Consumes the size returning the total number of bytes. Saturating at `u64::MAX`
+    ///
+    /// The same value as [ConfigSize::get_as_bytes] when it fits in a `usize`
+    pub fn into_bytes(self) -> u64 {
+        u64::try_from(self).unwrap_or(u64::MAX)
+    }
+    /// The total number of bits. Saturating at `u64::MAX`
+    pub fn get_as_bits(&self) -> u64 {
+        u64::try_from(self.total_bits()).unwrap_or(u64::MAX)
+    }
+    /// Breaks the size into the largest byte units. Such as `[(Mebibytes, 1), (Kibibytes, 512)]` for 1,572,864 bytes
+    ///
+    /// Units with a count of zero are left out. So zero is an empty `Vec`.
+    /// Bits that do not make up a whole byte are the last entry as [Unit::Bits]
+    pub fn decompose(&self) -> Vec<(Unit, usize)> {
+        let mut units: Vec<Unit> = Unit::iter().filter(|unit| !unit.is_bit_unit()).collect();
+        units.sort_by_key(|unit| std::cmp::Reverse(unit.bits()));
+        units.push(Unit::Bits);
+
+        let mut remaining = self.total_bits();
+        let mut parts = Vec::new();
+        for unit in units {
+            let unit_bits = unit.bits() as u128;
+            let count = remaining / unit_bits;
+            if count > 0 {
+                // Every part is at most the original size. So it fits
+                parts.push((unit, count as usize));
+                remaining %= unit_bits;
+            }
+        }
+        parts
+    }
+    /// The size in its stored unit along with the unit. Such as `(5, Unit::Mebibytes)` for `5MiB`
+    pub fn as_parts(&self) -> (usize, Unit) {
+        (self.size, self.unit)
+    }
+    fn total_bits(&self) -> u128 {
+        self.size as u128 * self.unit.bits() as u128
+    }
+    /// If the total number of bytes is at most [ConfigSize::MAX]
+    fn fits_in_usize(&self) -> bool {
+        self.total_bits() / 8 <= usize::MAX as u128
+    }
+    /// Creates a size from a number of bits. Using byte units when the total is a whole number of bytes
+    fn from_total_bits(bits: u128) -> Option<ConfigSize> {
+        if bits % 8 == 0 {
+            usize::try_from(bits / 8).ok().map(ConfigSize::from)
+        } else {
+            usize::try_from(bits).ok().map(|size| ConfigSize {
+                size,
+                unit: Unit::Bits,
+            })
+        }
+    }
+    /// Adds the two sizes. Returning `None` if the total overflows
+    ///
+    /// The result is in the largest unit that can exactly represent the total
+    pub fn checked_add(self, rhs: ConfigSize) -> Option<ConfigSize> {
+        Self::from_total_bits(self.total_bits().checked_add(rhs.total_bits())?)
+    }
+    /// Subtracts `rhs` from this size. Returning `None` if the result would be negative
+    ///
+    /// The result is in the largest unit that can exactly represent the total
+    pub fn checked_sub(self, rhs: ConfigSize) -> Option<ConfigSize> {
+        Self::from_total_bits(self.total_bits().checked_sub(rhs.total_bits())?)
+    }
+    /// Restricts the size to the range `min..=max`. Comparing by the total number of bits
+    ///
+    /// # Panics
+    /// If `min > max`. The same as [Ord::clamp]
+    pub fn clamp(self, min: ConfigSize, max: ConfigSize) -> ConfigSize {
+        Ord::clamp(self, min, max)
+    }
+    /// The same as [ConfigSize::clamp] but returns `None` if `min > max`
+    pub fn checked_clamp(self, min: ConfigSize, max: ConfigSize) -> Option<ConfigSize> {
+        (min <= max).then(|| Ord::clamp(self, min, max))
+    }
+    /// The same as [ConfigSize::clamp] but never panics. If `min > max` the bounds are swapped
+    pub fn saturating_clamp(self, min: ConfigSize, max: ConfigSize) -> ConfigSize {
+        if min <= max {
+            Ord::clamp(self, min, max)
+        } else {
+            Ord::clamp(self, max, min)
+        }
+    }
+    /// The largest size in the slice. `None` if it is empty
+    ///
+    /// Equal sizes in different units such as `1KiB` and `1024B` follow [Iterator::max]. The last one is returned
+    pub fn max_of(sizes: &[ConfigSize]) -> Option<ConfigSize> {
+        sizes.iter().copied().max()
+    }
+    /// The smallest size in the slice. `None` if it is empty
+    ///
+    /// Equal sizes in different units follow [Iterator::min]. The first one is returned
+    pub fn min_of(sizes: &[ConfigSize]) -> Option<ConfigSize> {
+        sizes.iter().copied().min()
+    }
+    /// Parses `s` and writes it back in the largest unit that represents it exactly. Such as `1KiB` for `1024 bytes`
+    ///
+    /// Accepts everything [ConfigSize::from_str_lenient] does. The result is a fixed point. Normalizing it again gives the same string.
+    /// Bit sizes stay in bit units unless they are a whole number of bytes
+    pub fn normalize_string(s: &str) -> Result<String, InvalidSizeError> {
+        Ok(Self::from_str_lenient(s)?.normalized().to_string())
+    }
+    /// The same size in the largest unit that represents it exactly
+    fn normalized(&self) -> ConfigSize {
+        let bits = self.total_bits();
+        let bit_unit = bits % 8 != 0;
+        Unit::iter()
+            .filter(|unit| unit.is_bit_unit() == bit_unit)
+            .filter(|unit| bits % unit.bits() as u128 == 0)
+            .max_by_key(|unit| unit.bits())
+            .map(|unit| ConfigSize {
+                size: (bits / unit.bits() as u128) as usize,
+                unit,
+            })
+            .unwrap_or(*self)
+    }
+    /// Iterates over `range` counting up by `step`. Such as `1KiB, 2KiB, 3KiB, 4KiB` for `1KiB..=4KiB` by `1KiB`
+    ///
+    /// [std::iter::Step] is unstable so ranges of sizes can not be iterated directly.
+    /// Each size is in the largest unit that represents it exactly. Stops early if the total overflows
+    ///
+    /// # Panics
+    /// If `step` is zero. The same as [Iterator::step_by]
+    pub fn iter_by(
+        range: RangeInclusive<ConfigSize>,
+        step: ConfigSize,
+    ) -> impl Iterator<Item = ConfigSize> {
+        assert!(!step.is_zero(), "step must not be zero");
+        let (start, end) = range.into_inner();
+        std::iter::successors(Some(start), move |size| size.checked_add(step))
+            .take_while(move |size| *size <= end)
+    }
+    /// Starts building a size with [ConfigSizeBuilder]
+    pub fn builder() -> ConfigSizeBuilder {
+        ConfigSizeBuilder::default()
+    }
+}
+/// A builder for [ConfigSize]
+///
+/// ```rust
+/// use tuxs_config_types::size_config::{ConfigSize, Unit};
+/// let size = ConfigSize::builder().size(5).unit(Unit::Mebibytes).build().unwrap();
+/// assert_eq!(size, ConfigSize::new_from_mebibytes(5));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigSizeBuilder {
+    size: Option<usize>,
+    unit: Unit,
+}
+impl ConfigSizeBuilder {
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = Some(size);
+        self
+    }
+    /// Defaults to [Unit::Bytes]
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+    /// Errors if no size was set or the total number of bytes does not fit in a `usize`
+    pub fn build(self) -> Result<ConfigSize, InvalidSizeError> {
+        let size = self.size.ok_or(InvalidSizeError::from("No size was set"))?;
+        let config_size = ConfigSize {
+            size,
+            unit: self.unit,
+        };
+        if !config_size.fits_in_usize() {
+            return Err(InvalidSizeError::from("Size is too large"));
+        }
+        Ok(config_size)
+    }
+}
+
+/// A [ConfigSize] that can also be unlimited
+///
+/// `"unlimited"`, `"inf"` and `"none"` (case insensitive) parse to [ConfigSizeLimit::Unlimited].
+/// Which is serialized as `"unlimited"`.
+/// A number is a limit in bytes
+///
+/// An unlimited value is greater than any finite size
+///
+/// # Examples in TOML
+/// ```toml
+/// max_upload = "100MiB"
+/// max_download = "unlimited"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIs)]
+pub enum ConfigSizeLimit {
+    Limited(ConfigSize),
+    Unlimited,
+}
+serde_via_string_or_number_types!(ConfigSizeLimit, |bytes| ConfigSizeLimit::Limited(
+    ConfigSize::from(usize::try_from(bytes).unwrap_or(usize::MAX))
+));
+impl ConfigSizeLimit {
+    /// The limit. `None` if unlimited
+    pub fn limit(&self) -> Option<ConfigSize> {
+        match self {
+            ConfigSizeLimit::Limited(size) => Some(*size),
+            ConfigSizeLimit::Unlimited => None,
+        }
+    }
+    /// If `size` is within the limit
+    pub fn allows(&self, size: ConfigSize) -> bool {
+        match self {
+            ConfigSizeLimit::Limited(limit) => size <= *limit,
+            ConfigSizeLimit::Unlimited => true,
+        }
+    }
+}
+impl Display for ConfigSizeLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSizeLimit::Limited(size) => size.fmt(f),
+            ConfigSizeLimit::Unlimited => f.write_str("unlimited"),
+        }
+    }
+}
+impl FromStr for ConfigSizeLimit {
+    type Err = InvalidSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if ["unlimited", "inf", "none"]
+            .iter()
+            .any(|sentinel| s.eq_ignore_ascii_case(sentinel))
+        {
+            return Ok(ConfigSizeLimit::Unlimited);
+        }
+        ConfigSize::from_str(s).map(ConfigSizeLimit::Limited)
+    }
+}
+extend_string_from_and_to!(ConfigSizeLimit, InvalidSizeError);
+impl From<ConfigSize> for ConfigSizeLimit {
+    fn from(value: ConfigSize) -> Self {
+        ConfigSizeLimit::Limited(value)
+    }
+}
+impl From<Option<ConfigSize>> for ConfigSizeLimit {
+    fn from(value: Option<ConfigSize>) -> Self {
+        value.map_or(ConfigSizeLimit::Unlimited, ConfigSizeLimit::Limited)
+    }
+}
+impl From<ConfigSizeLimit> for Option<ConfigSize> {
+    fn from(value: ConfigSizeLimit) -> Self {
+        value.limit()
+    }
+}
+impl PartialEq<ConfigSize> for ConfigSizeLimit {
+    fn eq(&self, other: &ConfigSize) -> bool {
+        self.limit().as_ref() == Some(other)
+    }
+}
+impl PartialOrd<ConfigSize> for ConfigSizeLimit {
+    fn partial_cmp(&self, other: &ConfigSize) -> Option<Ordering> {
+        match self {
+            ConfigSizeLimit::Limited(size) => size.partial_cmp(other),
+            ConfigSizeLimit::Unlimited => Some(Ordering::Greater),
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("{0}")]
+pub struct ConstraintViolation(pub String);
+/// A constraint on a size that can itself come from a config file
+///
+/// | Format         | Constraint                                   |
+/// |----------------|----------------------------------------------|
+/// | "min:1MiB"     | [ConfigSizeConstraint::Min]                  |
+/// | "max:1GiB"     | [ConfigSizeConstraint::Max]                  |
+/// | "exact:512MiB" | [ConfigSizeConstraint::Exact]                |
+/// | "1MiB..1GiB"   | [ConfigSizeConstraint::Range] both inclusive |
+///
+/// Sizes are compared by the total number of bits so `exact:1KiB` allows `1024B`
+///
+/// # Examples in TOML
+/// ```toml
+/// upload_size = "max:1GiB"
+/// buffer_size = "1MiB..1GiB"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIs)]
+pub enum ConfigSizeConstraint {
+    Min(ConfigSize),
+    Max(ConfigSize),
+    Exact(ConfigSize),
+    /// The minimum and maximum. Both inclusive
+    Range(ConfigSize, ConfigSize),
+}
+serde_via_string_types!(ConfigSizeConstraint);
+impl ConfigSizeConstraint {
+    /// Errors if `value` does not meet the constraint
+    pub fn check(&self, value: ConfigSize) -> Result<(), ConstraintViolation> {
+        let violation = match self {
+            ConfigSizeConstraint::Min(min) if value < *min => {
+                format!("{} is smaller than the minimum {}", value, min)
+            }
+            ConfigSizeConstraint::Max(max) if value > *max => {
+                format!("{} is larger than the maximum {}", value, max)
+            }
+            ConfigSizeConstraint::Exact(exact) if value != *exact => {
+                format!("{} is not exactly {}", value, exact)
+            }
+            ConfigSizeConstraint::Range(min, max) if value < *min || value > *max => {
+                format!("{} is not within {}..{}", value, min, max)
+            }
+            _ => return Ok(()),
+        };
+        Err(ConstraintViolation(violation))
+    }
+}
+impl Display for ConfigSizeConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSizeConstraint::Min(min) => write!(f, "min:{}", min),
+            ConfigSizeConstraint::Max(max) => write!(f, "max:{}", max),
+            ConfigSizeConstraint::Exact(exact) => write!(f, "exact:{}", exact),
+            ConfigSizeConstraint::Range(min, max) => write!(f, "{}..{}", min, max),
+        }
+    }
+}
+impl FromStr for ConfigSizeConstraint {
+    type Err = InvalidSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Moves the span of an error in the size part to its place in `s`
+        let parse_size = |size: &str, offset: usize| {
+            ConfigSize::from_str(size).map_err(|err| match err.span() {
+                Some(span) => err.with_span(span.start + offset..span.end + offset),
+                None => err,
+            })
+        };
+        if let Some((min, max)) = s.split_once("..") {
+            let max_offset = min.len() + 2;
+            let min = parse_size(min, 0)?;
+            let max = parse_size(max, max_offset)?;
+            if min > max {
+                return Err(InvalidSizeError::from(
+                    "The minimum is greater than the maximum",
+                ));
+            }
+            return Ok(ConfigSizeConstraint::Range(min, max));
+        }
+        let (kind, size) = s.split_once(':').ok_or(InvalidSizeError::from(
+            "Expected min:, max:, exact: or a range",
+        ))?;
+        let size = parse_size(size, kind.len() + 1)?;
+        match kind {
+            "min" => Ok(ConfigSizeConstraint::Min(size)),
+            "max" => Ok(ConfigSizeConstraint::Max(size)),
+            "exact" => Ok(ConfigSizeConstraint::Exact(size)),
+            _ => Err(InvalidSizeError::from("Unknown constraint").with_span(0..kind.len())),
+        }
+    }
+}
+extend_string_from_and_to!(ConfigSizeConstraint, InvalidSizeError);
+
+/// The smallest size. `None` if the iterator is empty. The same as [Iterator::min]
+pub fn min_size<I: IntoIterator<Item = ConfigSize>>(sizes: I) -> Option<ConfigSize> {
+    sizes.into_iter().min()
+}
+/// The largest size. `None` if the iterator is empty. The same as [Iterator::max]
+pub fn max_size<I: IntoIterator<Item = ConfigSize>>(sizes: I) -> Option<ConfigSize> {
+    sizes.into_iter().max()
+}
+/// The smallest and largest size in one pass. `None` if the iterator is empty
+///
+/// A single size is both the min and the max
+pub fn min_max_size<I: IntoIterator<Item = ConfigSize>>(
+    sizes: I,
+) -> Option<(ConfigSize, ConfigSize)> {
+    sizes.into_iter().fold(None, |min_max, size| match min_max {
+        None => Some((size, size)),
+        Some((min, max)) => Some((min.min(size), max.max(size))),
+    })
+}
+/// For `#[serde(deserialize_with = "...")]` on an `Option<ConfigSize>`. An empty string is `None`
+///
+/// Add `#[serde(default)]` so a missing field is also `None`
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use tuxs_config_types::size_config::{deserialize_optional_size, ConfigSize};
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default, deserialize_with = "deserialize_optional_size")]
+///     max_upload: Option<ConfigSize>,
+/// }
+/// let config: Config = serde_json::from_str(r#"{"max_upload": ""}"#).unwrap();
+/// assert!(config.max_upload.is_none());
+/// ```
+pub fn deserialize_optional_size<'de, D>(deserializer: D) -> Result<Option<ConfigSize>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::macros::deserialize_optional_from_str(deserializer)
+}
+/// Generates a module for `#[serde(with = "...")]` that writes a [ConfigSize] as an integer count of a unit
+macro_rules! serde_as_unit {
+    ($name:ident, $unit:expr, $unit_name:literal) => {
+        #[doc = concat!("Serializes a [ConfigSize] as an integer number of ", $unit_name, ".")]
+        #[doc = ""]
+        #[doc = concat!("Deserializes from either an integer number of ", $unit_name, " or a size string. Such as `\"512MiB\"`")]
+        #[doc = ""]
+        #[doc = "```rust"]
+        #[doc = "use serde::{Deserialize, Serialize};"]
+        #[doc = "use tuxs_config_types::size_config::ConfigSize;"]
+        #[doc = "#[derive(Serialize, Deserialize)]"]
+        #[doc = "struct Config {"]
+        #[doc = concat!("    #[serde(with = \"tuxs_config_types::size_config::", stringify!($name), "\")]")]
+        #[doc = "    max_upload: ConfigSize,"]
+        #[doc = "}"]
+        #[doc = "```"]
+        pub mod $name {
+            use super::{ConfigSize, Unit};
+
+            pub fn serialize<S>(size: &ConfigSize, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                super::serialize_as_unit(size, $unit, serializer)
+            }
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<ConfigSize, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                super::deserialize_as_unit(deserializer, $unit)
+            }
+        }
+    };
+}
+serde_as_unit!(serde_as_bytes, Unit::Bytes, "bytes");
+serde_as_unit!(serde_as_kibibytes, Unit::Kibibytes, "kibibytes");
+serde_as_unit!(serde_as_mebibytes, Unit::Mebibytes, "mebibytes");
+
+pub(crate) fn serialize_as_unit<S>(
+    size: &ConfigSize,
+    unit: Unit,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::Error;
+    let bits = size.total_bits();
+    let unit_bits = unit.bits() as u128;
+    if bits % unit_bits != 0 {
+        return Err(S::Error::custom(format!(
+            "{} is not a whole number of {}",
+            size, unit
+        )));
     }
+    let count = u64::try_from(bits / unit_bits).map_err(S::Error::custom)?;
+    serializer.serialize_u64(count)
 }
+fn deserialize_as_unit<'de, D>(deserializer: D, unit: Unit) -> Result<ConfigSize, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct Visitor(Unit);
+    impl<'de> serde::de::Visitor<'de> for Visitor {
+        type Value = ConfigSize;
 
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(
+                formatter,
+                "an integer number of {} or a size string",
+                self.0
+            )
+        }
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            ConfigSize::from_str(v).map_err(E::custom)
+        }
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            let size = usize::try_from(v).map_err(E::custom)?;
+            Ok(ConfigSize { size, unit: self.0 })
+        }
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            let size = usize::try_from(v).map_err(E::custom)?;
+            Ok(ConfigSize { size, unit: self.0 })
+        }
+    }
+    // Formats that are not self describing only ever contain the integer that serialize_as_unit wrote
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(Visitor(unit))
+    } else {
+        deserializer.deserialize_u64(Visitor(unit))
+    }
+}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,6 +1384,7 @@ mod tests {
     use strum::IntoEnumIterator;
     #[test]
     pub fn test_unit_regex() {
+        #[cfg(feature = "regex")]
         println!("{}", Unit::create_regex_string());
         for unit in Unit::iter() {
             let number = rand::thread_rng().gen_range(100..10000);
@@ -217,4 +1416,1050 @@ mod tests {
             println!("{:?} -> {} -> {:?}", test.size, string, test2.size)
         }
     }
+    #[test]
+    pub fn test_constants() {
+        assert_eq!(ConfigSize::ZERO.get_as_bytes(), 0);
+        assert_eq!(ConfigSize::MAX.get_as_bytes(), usize::MAX);
+        for _ in 0..10 {
+            let number = rand::thread_rng().gen_range(1..10000);
+            let size = ConfigSize::new_from_kibibytes(number);
+            assert!(ConfigSize::ZERO < size);
+            assert!(size < ConfigSize::MAX);
+            assert_eq!(size.clamp(ConfigSize::ZERO, ConfigSize::MAX), size);
+        }
+    }
+    #[test]
+    pub fn test_byte_total_limit() {
+        let max = ConfigSize::from_str(&format!("{}B", usize::MAX)).unwrap();
+        assert_eq!(max, ConfigSize::MAX);
+        assert!(ConfigSize::from_str(&format!("{}Mib", usize::MAX)).is_err());
+        #[cfg(target_pointer_width = "64")]
+        {
+            let error = ConfigSize::from_str("16777216TiB").unwrap_err();
+            assert_eq!(error.context(), "Size is too large");
+            assert!(ConfigSize::from_str("16777215TiB").unwrap() <= ConfigSize::MAX);
+        }
+        // Built directly instead of parsed. Conversions saturate instead of overflowing
+        let huge = ConfigSize::from_tib(usize::MAX);
+        assert!(huge > ConfigSize::MAX);
+        assert_eq!(huge.get_as_bytes(), usize::MAX);
+        assert!(huge.human_readable().ends_with("EiB"));
+        assert!(ConfigSize::builder()
+            .size(usize::MAX)
+            .unit(Unit::Tebibytes)
+            .build()
+            .is_err());
+    }
+    #[test]
+    pub fn test_strict() {
+        assert!(ConfigSize::from_str_strict("007MiB").is_err());
+        assert!(ConfigSize::from_str_strict("00").is_err());
+        assert_eq!(
+            ConfigSize::from_str("007MiB").unwrap(),
+            ConfigSize::new_from_mebibytes(7)
+        );
+        assert_eq!(
+            ConfigSize::from_str_strict("0").unwrap(),
+            ConfigSize::from_str("0").unwrap()
+        );
+        assert_eq!(
+            ConfigSize::from_str_strict("700KiB").unwrap(),
+            ConfigSize::new_from_kibibytes(700)
+        );
+    }
+    #[test]
+    pub fn test_arithmetic() {
+        let sum = ConfigSize::new_from_kibibytes(1) + ConfigSize::new_from_bytes(1024);
+        assert_eq!(sum, ConfigSize::new_from_kibibytes(2));
+        let sum = ConfigSize::new_from_kibibytes(1) + ConfigSize::new_from_bytes(1);
+        assert_eq!(sum, ConfigSize::new_from_bytes(1025));
+        let difference = ConfigSize::new_from_kibibytes(2) - ConfigSize::new_from_bytes(1024);
+        assert_eq!(difference, ConfigSize::new_from_kibibytes(1));
+
+        assert!(ConfigSize::MAX
+            .checked_add(ConfigSize::new_from_bytes(1))
+            .is_none());
+        assert!(ConfigSize::ZERO
+            .checked_sub(ConfigSize::new_from_bytes(1))
+            .is_none());
+    }
+    #[test]
+    pub fn test_bit_units() {
+        let bits = ConfigSize::from_str("8b").unwrap();
+        let bytes = ConfigSize::from_str("1B").unwrap();
+        assert_eq!(bits.unit, Unit::Bits);
+        assert_eq!(bytes.unit, Unit::Bytes);
+        assert_eq!(bits.get_as_bits(), bytes.get_as_bits());
+        assert_eq!(bits.get_as_bytes(), bytes.get_as_bytes());
+        assert_eq!(bits.cmp(&bytes), Ordering::Equal);
+
+        let mebibits = ConfigSize::from_str("100Mib").unwrap();
+        assert_eq!(mebibits.unit, Unit::Mebibits);
+        assert_eq!(mebibits.get_as_bits(), 100 * 1024 * 1024);
+        assert_eq!(mebibits.get_as_bytes(), 100 * 128 * 1024);
+        let kibibits = ConfigSize::from_str("8Kib").unwrap();
+        assert_eq!(kibibits.get_as_bytes(), 1024);
+
+        let sum = ConfigSize::from_str("4b").unwrap() + ConfigSize::from_str("4b").unwrap();
+        assert_eq!(sum, bytes);
+        let sum = ConfigSize::from_str("3b").unwrap() + ConfigSize::from_str("1B").unwrap();
+        assert_eq!(sum.to_string(), "11b");
+    }
+    #[test]
+    pub fn test_as_parts() {
+        let size = ConfigSize::from_str("5MiB").unwrap();
+        assert_eq!(size.as_parts(), (5, Unit::Mebibytes));
+        let size = ConfigSize::from_str("100").unwrap();
+        assert_eq!(size.as_parts(), (100, Unit::Bytes));
+    }
+    #[test]
+    pub fn test_sum() {
+        let sizes = vec![
+            ConfigSize::new_from_mebibytes(1),
+            ConfigSize::new_from_kibibytes(1023),
+            ConfigSize::new_from_bytes(1024),
+        ];
+        let total: ConfigSize = sizes.iter().sum();
+        assert_eq!(total, ConfigSize::new_from_mebibytes(2));
+        let total: ConfigSize = sizes.into_iter().sum();
+        assert_eq!(total, ConfigSize::new_from_mebibytes(2));
+
+        let empty: ConfigSize = Vec::<ConfigSize>::new().into_iter().sum();
+        assert_eq!(empty, ConfigSize::ZERO);
+    }
+    #[test]
+    pub fn test_size_limit() {
+        for sentinel in ["unlimited", "inf", "none", "Unlimited", "INF"] {
+            let limit = ConfigSizeLimit::from_str(sentinel).unwrap();
+            assert!(limit.is_unlimited());
+            assert_eq!(limit.to_string(), "unlimited");
+        }
+        let limit = ConfigSizeLimit::from_str("100MiB").unwrap();
+        assert_eq!(limit.limit(), Some(ConfigSize::new_from_mebibytes(100)));
+        assert!(ConfigSizeLimit::from_str("unbounded").is_err());
+
+        let unlimited = ConfigSizeLimit::Unlimited;
+        assert!(unlimited > limit);
+        assert!(unlimited > ConfigSize::MAX);
+        assert!(limit > ConfigSize::new_from_mebibytes(99));
+        assert!(limit < ConfigSize::new_from_mebibytes(101));
+        assert!(limit.allows(ConfigSize::new_from_mebibytes(100)));
+        assert!(!limit.allows(ConfigSize::new_from_mebibytes(101)));
+        assert!(unlimited.allows(ConfigSize::MAX));
+
+        let json = serde_json::to_string(&unlimited).unwrap();
+        assert_eq!(json, r#""unlimited""#);
+        let parsed: ConfigSizeLimit = serde_json::from_str(r#""inf""#).unwrap();
+        assert_eq!(parsed, unlimited);
+    }
+    #[test]
+    pub fn test_error_span() {
+        let input = "100XB";
+        let error = ConfigSize::from_str(input).unwrap_err();
+        let span = error.span().unwrap();
+        assert_eq!(span, 3..5);
+        assert_eq!(&input[span], "XB");
+
+        let error = ConfigSize::from_str_strict("007MiB").unwrap_err();
+        assert_eq!(error.span(), Some(0..3));
+        let error = ConfigSize::from_str("MiB").unwrap_err();
+        assert_eq!(error.span(), Some(0..3));
+        let error = ConfigSize::from_str("99999999999999999999999B").unwrap_err();
+        assert_eq!(error.span(), Some(0..23));
+    }
+    #[test]
+    pub fn test_human_readable() {
+        let human_readable = |s: &str| ConfigSize::from_str(s).unwrap().human_readable();
+        assert_eq!(human_readable("1536MiB"), "1.5 GiB");
+        assert_eq!(human_readable("0"), "0 Bytes");
+        assert_eq!(human_readable("1"), "1 Byte");
+        assert_eq!(human_readable("512"), "512 Bytes");
+        assert_eq!(human_readable("1024"), "1 KiB");
+        assert_eq!(human_readable("1024KiB"), "1 MiB");
+        assert_eq!(human_readable("1048576KiB"), "1 GiB");
+        assert_eq!(human_readable("1025KiB"), "1 MiB");
+        assert_eq!(human_readable("1100KiB"), "1.07 MiB");
+        assert_eq!(human_readable("1048575"), "1 MiB");
+    }
+    #[test]
+    pub fn test_serialize_matches_display() {
+        for unit in Unit::iter() {
+            let size = ConfigSize { size: 512, unit };
+            assert_eq!(
+                serde_json::to_string(&size).unwrap(),
+                serde_json::to_string(&size.to_string()).unwrap()
+            );
+        }
+    }
+    #[test]
+    pub fn test_compare_with_bytes() {
+        let size = ConfigSize::from_str("1MiB").unwrap();
+        assert!(size > 1000u64);
+        assert!(size < 2_000_000u64);
+        assert!(size == 1_048_576u64);
+        assert!(1_048_576u64 == size);
+        assert!(1000u64 < size);
+        assert!(size != 1_048_575usize);
+        assert!(ConfigSize::from_str("4b").unwrap() < 1u64);
+        assert!(ConfigSize::from_str("4b").unwrap() > 0u64);
+    }
+    #[test]
+    pub fn test_unit_navigation() {
+        assert_eq!(Unit::Kibibytes.next_larger(), Some(Unit::Mebibytes));
+        assert_eq!(Unit::Kibibytes.next_smaller(), Some(Unit::Bytes));
+        assert_eq!(Unit::Kibibits.next_larger(), Some(Unit::Mebibits));
+        assert_eq!(Unit::Kibibits.next_smaller(), Some(Unit::Bits));
+        assert_eq!(Unit::Mebibytes.next_larger(), Some(Unit::Gibibytes));
+        assert_eq!(Unit::Tebibytes.next_larger(), None);
+        assert_eq!(Unit::Bytes.next_smaller(), None);
+        assert_eq!(Unit::Mebibits.next_larger(), None);
+        assert_eq!(Unit::Bits.next_smaller(), None);
+    }
+    #[test]
+    pub fn test_parse_edge_cases() {
+        // Both parsers must accept and reject the same inputs
+        for (input, expected) in [
+            ("0", Ok((0, Unit::Bytes))),
+            ("10", Ok((10, Unit::Bytes))),
+            ("10KiB", Ok((10, Unit::Kibibytes))),
+            ("10Kib", Ok((10, Unit::Kibibits))),
+            ("10b", Ok((10, Unit::Bits))),
+            ("", Err(0..0)),
+            ("KiB", Err(0..3)),
+            ("10KiBs", Err(2..6)),
+            ("10 KiB", Err(2..6)),
+            ("10Ki", Err(2..4)),
+            ("10KiB\n", Err(0..6)),
+            ("-10KiB", Err(0..6)),
+        ] {
+            let result = ConfigSize::from_str(input)
+                .map(|size| (size.size, size.unit))
+                .map_err(|err| err.span().unwrap());
+            assert_eq!(result, expected, "Input: {:?}", input);
+            let by_hand = ConfigSize::from_str_by_hand(input)
+                .map(|size| (size.size, size.unit))
+                .map_err(|err| err.span().unwrap());
+            assert_eq!(by_hand, expected, "Input: {:?}", input);
+            #[cfg(feature = "regex")]
+            {
+                let with_regex = ConfigSize::from_str_with_regex(input)
+                    .map(|size| (size.size, size.unit))
+                    .map_err(|err| err.span().unwrap());
+                assert_eq!(with_regex, expected, "Input: {:?}", input);
+            }
+        }
+    }
+    #[test]
+    pub fn test_builder() {
+        let size = ConfigSize::builder()
+            .size(5)
+            .unit(Unit::Mebibytes)
+            .build()
+            .unwrap();
+        assert_eq!(size, ConfigSize::new_from_mebibytes(5));
+        assert_eq!(
+            ConfigSize::builder().size(10).build().unwrap(),
+            ConfigSize::new_from_bytes(10)
+        );
+        assert_eq!(
+            ConfigSize::builder()
+                .unit(Unit::Kibibytes)
+                .size(3)
+                .build()
+                .unwrap(),
+            ConfigSize::new_from_kibibytes(3)
+        );
+        assert!(ConfigSize::builder().unit(Unit::Kibibytes).build().is_err());
+        assert!(ConfigSize::builder()
+            .size(usize::MAX)
+            .unit(Unit::Mebibytes)
+            .build()
+            .is_err());
+    }
+    #[test]
+    pub fn test_from_str_with_locale() {
+        let expected = ConfigSize::new_from_mebibytes(1024);
+        for (input, locale) in [
+            ("1,024 MiB", SizeLocale::En),
+            ("1,024MiB", SizeLocale::En),
+            ("1024MiB", SizeLocale::En),
+            ("1.024 MiB", SizeLocale::De),
+            ("1 024 MiB", SizeLocale::Fr),
+            ("1\u{a0}024\u{a0}MiB", SizeLocale::Fr),
+            ("1\u{202f}024MiB", SizeLocale::Fr),
+        ] {
+            assert_eq!(
+                ConfigSize::from_str_with_locale(input, locale).unwrap(),
+                expected,
+                "Input: {:?}",
+                input
+            );
+        }
+        assert_eq!(
+            ConfigSize::from_str_with_locale("1,048,576", SizeLocale::En).unwrap(),
+            ConfigSize::new_from_bytes(1048576)
+        );
+        // A German decimal comma must not be read as an English thousands separator
+        assert!(ConfigSize::from_str_with_locale("1,5 MiB", SizeLocale::En).is_err());
+        assert!(ConfigSize::from_str_with_locale("1,5 MiB", SizeLocale::De).is_err());
+        assert!(ConfigSize::from_str_with_locale("1.5 MiB", SizeLocale::En).is_err());
+        assert!(ConfigSize::from_str_with_locale("1,0240 MiB", SizeLocale::En).is_err());
+        assert!(ConfigSize::from_str_with_locale(",024 MiB", SizeLocale::En).is_err());
+        assert!(ConfigSize::from_str_with_locale("1,024 XB", SizeLocale::En).is_err());
+
+        for (input, locale) in [
+            ("1 024 MiB", SizeLocale::En),
+            ("1\u{a0}024MiB", SizeLocale::De),
+            ("1,024 MiB", SizeLocale::Fr),
+        ] {
+            let error = ConfigSize::from_str_with_locale(input, locale).unwrap_err();
+            let expected = if locale == SizeLocale::Fr {
+                "Sizes must be whole numbers"
+            } else {
+                "Thousands separator does not match the locale"
+            };
+            assert_eq!(error.context(), expected, "Input: {:?}", input);
+            assert_eq!(error.input(), Some(input));
+            assert_eq!(error.span(), None);
+        }
+        let error = ConfigSize::from_str_with_locale("1,024 XB", SizeLocale::En).unwrap_err();
+        assert_eq!(error.context(), "Unknown unit");
+        assert_eq!(error.input(), Some("1,024 XB"));
+        assert_eq!(error.span(), None);
+        // The default parser still only accepts canonical input
+        assert!(ConfigSize::from_str("1,024MiB").is_err());
+    }
+    #[test]
+    pub fn test_from_str_permissive() {
+        // MB is a mebibyte. Not a megabyte
+        let size = ConfigSize::from_str_permissive("512MB").unwrap();
+        assert_eq!(size, ConfigSize::new_from_mebibytes(512));
+        assert_eq!(size.get_as_bytes(), 512 * 1_048_576);
+        assert_ne!(size.get_as_bytes(), 512 * 1_000_000);
+
+        for (input, unit) in [
+            ("1K", Unit::Kibibytes),
+            ("1KB", Unit::Kibibytes),
+            ("1M", Unit::Mebibytes),
+            ("1G", Unit::Gibibytes),
+            ("1GB", Unit::Gibibytes),
+            ("1T", Unit::Tebibytes),
+            ("1TB", Unit::Tebibytes),
+            ("1MiB", Unit::Mebibytes),
+            ("1", Unit::Bytes),
+        ] {
+            let size = ConfigSize::from_str_permissive(input).unwrap();
+            assert_eq!(size.as_parts(), (1, unit), "Input: {:?}", input);
+        }
+        assert_eq!(
+            ConfigSize::from_str_permissive("1G")
+                .unwrap()
+                .get_as_bytes(),
+            1 << 30
+        );
+        assert!(ConfigSize::from_str_permissive("MB").is_err());
+        assert!(ConfigSize::from_str_permissive("1XB").is_err());
+        for (input, expected) in [
+            ("5 MiB", ConfigSize::new_from_mebibytes(5)),
+            ("5 MB", ConfigSize::new_from_mebibytes(5)),
+            ("5k", ConfigSize::new_from_kibibytes(5)),
+            ("5 kB", ConfigSize::new_from_kibibytes(5)),
+            ("5g", ConfigSize::from_gib(5)),
+            ("8 b", ConfigSize::new_from_bytes(1)),
+        ] {
+            let size = ConfigSize::from_str_permissive(input).unwrap();
+            assert_eq!(size, expected, "Input: {:?}", input);
+        }
+        // A lower case b is bits. So this is not kilobytes
+        assert!(ConfigSize::from_str_permissive("5kb").is_err());
+        let error = ConfigSize::from_str_permissive("5 XB").unwrap_err();
+        assert_eq!(error.input(), Some("5 XB"));
+        assert_eq!(error.span(), Some(2..4));
+        assert!(ConfigSize::from_str_permissive("5 ").is_err());
+        assert!(ConfigSize::from_str_with_alias_mode("5 MiB", AliasMode::Strict).is_err());
+        // The default parser stays strict
+        assert!(ConfigSize::from_str("512MB").is_err());
+        assert!(ConfigSize::from_str_with_alias_mode("512MB", AliasMode::Strict).is_err());
+    }
+    #[test]
+    pub fn test_to_grouped_string() {
+        assert_eq!(
+            ConfigSize::new_from_bytes(1048576).to_grouped_string(),
+            "1,048,576B"
+        );
+        assert_eq!(
+            ConfigSize::new_from_kibibytes(123456).to_grouped_string(),
+            "123,456KiB"
+        );
+        assert_eq!(ConfigSize::new_from_bytes(999).to_grouped_string(), "999B");
+        assert_eq!(
+            ConfigSize::new_from_bytes(1000).to_grouped_string(),
+            "1,000B"
+        );
+        assert_eq!(ConfigSize::ZERO.to_grouped_string(), "0B");
+        // Display is unchanged
+        assert_eq!(ConfigSize::new_from_bytes(1048576).to_string(), "1048576B");
+    }
+    #[test]
+    pub fn test_clamp() {
+        let min = ConfigSize::new_from_kibibytes(1);
+        let max = ConfigSize::new_from_mebibytes(1);
+        let inside = ConfigSize::new_from_kibibytes(512);
+        assert_eq!(inside.clamp(min, max), inside);
+        assert_eq!(ConfigSize::new_from_bytes(10).clamp(min, max), min);
+        assert_eq!(ConfigSize::new_from_mebibytes(2).clamp(min, max), max);
+        // Equal to a bound but in a different unit. The value is kept
+        let bound = ConfigSize::new_from_bytes(1024);
+        assert_eq!(bound.clamp(min, max), bound);
+        assert_eq!(min.clamp(min, max), min);
+        assert_eq!(max.clamp(min, max), max);
+
+        assert_eq!(inside.checked_clamp(min, max), Some(inside));
+        assert_eq!(inside.checked_clamp(max, min), None);
+        assert_eq!(ConfigSize::ZERO.saturating_clamp(max, min), min);
+        assert_eq!(ConfigSize::MAX.saturating_clamp(max, min), max);
+        assert_eq!(inside.saturating_clamp(max, min), inside);
+    }
+    #[test]
+    #[should_panic]
+    pub fn test_clamp_invalid_range() {
+        ConfigSize::ZERO.clamp(ConfigSize::MAX, ConfigSize::ZERO);
+    }
+    #[test]
+    pub fn test_into_f64() {
+        assert_eq!(f64::from(ConfigSize::from_str("1KiB").unwrap()), 1024.0);
+        assert_eq!(f64::from(ConfigSize::ZERO), 0.0);
+        assert_eq!(f64::from(ConfigSize::from_str("12b").unwrap()), 1.5);
+    }
+    #[test]
+    pub fn test_serde_as_unit() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Config {
+            #[serde(with = "serde_as_bytes")]
+            bytes: ConfigSize,
+            #[serde(with = "serde_as_kibibytes")]
+            kibibytes: ConfigSize,
+            #[serde(with = "serde_as_mebibytes")]
+            mebibytes: ConfigSize,
+        }
+        let config = Config {
+            bytes: ConfigSize::new_from_kibibytes(1),
+            kibibytes: ConfigSize::new_from_mebibytes(2),
+            mebibytes: ConfigSize::new_from_mebibytes(3),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"bytes":1024,"kibibytes":2048,"mebibytes":3}"#);
+
+        let config: Config =
+            serde_json::from_str(r#"{"bytes":10,"kibibytes":"1MiB","mebibytes":5}"#).unwrap();
+        assert_eq!(config.bytes, ConfigSize::new_from_bytes(10));
+        assert_eq!(config.kibibytes, ConfigSize::new_from_mebibytes(1));
+        assert_eq!(config.mebibytes, ConfigSize::new_from_mebibytes(5));
+
+        // Not a whole number of mebibytes
+        let config = Config {
+            bytes: ConfigSize::ZERO,
+            kibibytes: ConfigSize::ZERO,
+            mebibytes: ConfigSize::new_from_kibibytes(1),
+        };
+        assert!(serde_json::to_string(&config).is_err());
+        assert!(
+            serde_json::from_str::<Config>(r#"{"bytes":-1,"kibibytes":1,"mebibytes":1}"#).is_err()
+        );
+    }
+    #[test]
+    pub fn test_from_str_lenient() {
+        for (input, expected) in [
+            ("10bytes", ConfigSize::new_from_bytes(10)),
+            ("10 bytes", ConfigSize::new_from_bytes(10)),
+            ("1 byte", ConfigSize::new_from_bytes(1)),
+            ("5 Mebibytes", ConfigSize::new_from_mebibytes(5)),
+            ("2kibibyte", ConfigSize::new_from_kibibytes(2)),
+            ("8 bits", ConfigSize::from_str("8b").unwrap()),
+            ("5MiB", ConfigSize::new_from_mebibytes(5)),
+            ("5 MiB", ConfigSize::new_from_mebibytes(5)),
+            ("100 Ki", ConfigSize::new_from_kibibytes(100)),
+        ] {
+            let size = ConfigSize::from_str_lenient(input).unwrap();
+            assert_eq!(size, expected, "Input: {:?}", input);
+        }
+        assert_eq!(
+            ConfigSize::from_str_lenient("10 bytes")
+                .unwrap()
+                .to_string(),
+            "10B"
+        );
+        for input in ["10kb", "10 mb", "10 megabytes", "bytes", "10 byt", "10 "] {
+            assert!(
+                ConfigSize::from_str_lenient(input).is_err(),
+                "Input: {:?}",
+                input
+            );
+        }
+        assert!(ConfigSize::from_str("10bytes").is_err());
+    }
+    #[test]
+    pub fn test_decompose() {
+        let size = ConfigSize::new_from_bytes(1_572_864);
+        assert_eq!(
+            size.decompose(),
+            vec![(Unit::Mebibytes, 1), (Unit::Kibibytes, 512)]
+        );
+        assert_eq!(
+            ConfigSize::from_str("12b").unwrap().decompose(),
+            vec![(Unit::Bytes, 1), (Unit::Bits, 4)]
+        );
+        assert_eq!(
+            ConfigSize::from_str("2048GiB").unwrap().decompose(),
+            vec![(Unit::Tebibytes, 2)]
+        );
+        assert!(ConfigSize::ZERO.decompose().is_empty());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let size = ConfigSize {
+                size: rng.gen_range(0..usize::MAX / 8),
+                unit: Unit::Bits,
+            };
+            let total: ConfigSize = size
+                .decompose()
+                .into_iter()
+                .map(|(unit, size)| ConfigSize { size, unit })
+                .sum();
+            assert_eq!(total.cmp(&size), Ordering::Equal);
+            assert_eq!(total.get_as_bits(), size.get_as_bits());
+        }
+    }
+    #[test]
+    pub fn test_unit_try_from_multiplier() {
+        assert_eq!(Unit::try_from(1).unwrap(), Unit::Bytes);
+        assert_eq!(Unit::try_from(1024).unwrap(), Unit::Kibibytes);
+        assert_eq!(Unit::try_from(1048576).unwrap(), Unit::Mebibytes);
+        assert!(Unit::try_from(1500).is_err());
+        assert!(Unit::try_from(0).is_err());
+        for unit in Unit::iter().filter(|unit| !unit.is_bit_unit()) {
+            let multiplier = (unit.bits() / 8) as usize;
+            assert_eq!(Unit::try_from(multiplier).unwrap(), unit);
+        }
+    }
+    #[test]
+    pub fn test_into_integers() {
+        let size = ConfigSize::from_str("1KiB").unwrap();
+        assert_eq!(usize::try_from(size).unwrap(), 1024);
+        assert_eq!(u64::try_from(size).unwrap(), 1024);
+        assert_eq!(u128::from(size), 1024);
+        assert_eq!(u128::from(ConfigSize::from_str("12b").unwrap()), 1);
+
+        let huge = ConfigSize {
+            size: usize::MAX,
+            unit: Unit::Tebibytes,
+        };
+        assert!(usize::try_from(huge).is_err());
+        assert!(u64::try_from(huge).is_err());
+        assert_eq!(u128::from(huge), usize::MAX as u128 * (1 << 40));
+    }
+    #[test]
+    pub fn test_eq_across_units() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        fn hash(size: &ConfigSize) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            size.hash(&mut hasher);
+            hasher.finish()
+        }
+        let kibibyte = ConfigSize::new_from_kibibytes(1);
+        let bytes = ConfigSize::new_from_bytes(1024);
+        let bits = ConfigSize::from_str("8Kib").unwrap();
+        assert_eq!(kibibyte, bytes);
+        assert_eq!(kibibyte, bits);
+        assert_eq!(kibibyte.cmp(&bytes), Ordering::Equal);
+        assert_eq!(hash(&kibibyte), hash(&bytes));
+        assert_eq!(hash(&kibibyte), hash(&bits));
+        assert_ne!(kibibyte, ConfigSize::new_from_bytes(1023));
+
+        let set: std::collections::HashSet<ConfigSize> = [kibibyte, bytes, bits].into();
+        assert_eq!(set.len(), 1);
+        // The unit is still kept
+        assert_eq!(kibibyte.as_parts(), (1, Unit::Kibibytes));
+    }
+    #[test]
+    pub fn test_into_bytes() {
+        for input in ["0B", "1KiB", "5MiB", "2GiB", "12b", "3Mib"] {
+            let size = ConfigSize::from_str(input).unwrap();
+            assert_eq!(size.into_bytes(), size.get_as_bytes() as u64);
+        }
+        let huge = ConfigSize {
+            size: usize::MAX,
+            unit: Unit::Tebibytes,
+        };
+        assert_eq!(huge.into_bytes(), u64::MAX);
+    }
+    #[test]
+    pub fn test_ratio() {
+        let half = ConfigSize::from_str("512MiB").unwrap();
+        let total = ConfigSize::from_str("1GiB").unwrap();
+        assert_eq!(ConfigSize::ratio(half, total), 0.5);
+        assert_eq!(half.percent_of(&total), 50.0);
+        assert_eq!(total.percent_of(&half), 200.0);
+        assert_eq!(ConfigSize::ratio(ConfigSize::ZERO, total), 0.0);
+
+        assert_eq!(ConfigSize::ratio(half, ConfigSize::ZERO), f64::INFINITY);
+        assert!(ConfigSize::ratio(ConfigSize::ZERO, ConfigSize::ZERO).is_nan());
+    }
+    #[test]
+    pub fn test_requires_digits() {
+        for input in ["MiB", "B", "", "MiB5", "5MiB5", " 5MiB", "5MiB "] {
+            let error = ConfigSize::from_str(input).unwrap_err();
+            assert!(error.span().is_some(), "Input: {:?}", input);
+        }
+        let error = ConfigSize::from_str("MiB").unwrap_err();
+        assert_eq!(error.span(), Some(0..3));
+        assert!(error
+            .to_string()
+            .starts_with("Does not meet requirements for a size"));
+    }
+    #[test]
+    pub fn test_zero_display() {
+        for input in ["0B", "0MiB", "0TiB", "0b", "0Kib"] {
+            let size = ConfigSize::from_str(input).unwrap();
+            assert!(size.is_zero());
+            assert_eq!(size.to_string(), "0B");
+            assert_eq!(serde_json::to_string(&size).unwrap(), r#""0B""#);
+        }
+        assert!(!ConfigSize::from_str("1b").unwrap().is_zero());
+    }
+    #[test]
+    pub fn test_deserialize_optional_size() {
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            #[serde(default, deserialize_with = "deserialize_optional_size")]
+            max_upload: Option<ConfigSize>,
+        }
+        let parse = |json: &str| serde_json::from_str::<Config>(json).map(|c| c.max_upload);
+        assert_eq!(parse(r#"{"max_upload": ""}"#).unwrap(), None);
+        assert_eq!(parse(r#"{"max_upload": null}"#).unwrap(), None);
+        assert_eq!(parse(r#"{}"#).unwrap(), None);
+        assert_eq!(
+            parse(r#"{"max_upload": "1MiB"}"#).unwrap(),
+            Some(ConfigSize::new_from_mebibytes(1))
+        );
+        assert_eq!(
+            parse(r#"{"max_upload": 1024}"#).unwrap(),
+            Some(ConfigSize::new_from_bytes(1024))
+        );
+        assert!(parse(r#"{"max_upload": "1XB"}"#).is_err());
+    }
+    #[test]
+    pub fn test_max_of_min_of() {
+        let sizes = [
+            ConfigSize::from_str("2KiB").unwrap(),
+            ConfigSize::from_str("1MiB").unwrap(),
+            ConfigSize::from_str("512B").unwrap(),
+            ConfigSize::from_str("4096b").unwrap(),
+        ];
+        assert_eq!(ConfigSize::max_of(&sizes).unwrap().to_string(), "1MiB");
+        assert_eq!(ConfigSize::min_of(&sizes).unwrap().to_string(), "512B");
+        assert_eq!(ConfigSize::max_of(&[]), None);
+        assert_eq!(ConfigSize::min_of(&[]), None);
+
+        let equal = [
+            ConfigSize::from_str("1KiB").unwrap(),
+            ConfigSize::from_str("1024B").unwrap(),
+        ];
+        assert_eq!(ConfigSize::max_of(&equal).unwrap().to_string(), "1024B");
+        assert_eq!(ConfigSize::min_of(&equal).unwrap().to_string(), "1KiB");
+    }
+    #[test]
+    pub fn test_alternate_format() {
+        let size = ConfigSize::new_from_mebibytes(1);
+        assert_eq!(format!("{}", size), "1MiB");
+        assert_eq!(format!("{:#}", size), "1 mebibyte");
+        assert_eq!(
+            format!("{:#}", ConfigSize::new_from_mebibytes(2)),
+            "2 mebibytes"
+        );
+        assert_eq!(
+            format!("{:#}", ConfigSize::from_str("3b").unwrap()),
+            "3 bits"
+        );
+        assert_eq!(
+            format!("{:#}", ConfigSize::from_str("0KiB").unwrap()),
+            "0 bytes"
+        );
+
+        assert_eq!(format!("{:x}", ConfigSize::new_from_kibibytes(1)), "400");
+        assert_eq!(format!("{:#x}", ConfigSize::new_from_kibibytes(1)), "0x400");
+        assert_eq!(format!("{:b}", ConfigSize::new_from_bytes(5)), "101");
+        assert_eq!(
+            format!("{:#010b}", ConfigSize::new_from_bytes(5)),
+            "0b00000101"
+        );
+        assert_eq!(format!("{:x}", ConfigSize::from_str("20b").unwrap()), "2");
+    }
+    #[test]
+    pub fn test_lenient_missing_byte_suffix() {
+        assert_eq!(
+            ConfigSize::from_str_lenient("100Ki").unwrap(),
+            ConfigSize::new_from_kibibytes(100)
+        );
+        assert_eq!(
+            ConfigSize::from_str_lenient("100Mi").unwrap(),
+            ConfigSize::new_from_mebibytes(100)
+        );
+        assert_eq!(
+            ConfigSize::from_str_lenient("1Gi").unwrap().to_string(),
+            "1GiB"
+        );
+        assert_eq!(
+            ConfigSize::from_str_lenient("1Mib").unwrap().to_string(),
+            "1Mib"
+        );
+        assert!(ConfigSize::from_str_lenient("1i").is_err());
+
+        assert!(ConfigSize::from_str("100Ki").is_err());
+        assert!(ConfigSize::from_str("100Mi").is_err());
+        assert!(ConfigSize::from_str_strict("100Ki").is_err());
+    }
+    #[test]
+    pub fn test_min_max_size() {
+        assert_eq!(min_size(Vec::<ConfigSize>::new()), None);
+        assert_eq!(max_size(Vec::<ConfigSize>::new()), None);
+        assert_eq!(min_max_size(Vec::<ConfigSize>::new()), None);
+
+        let one = ConfigSize::new_from_kibibytes(1);
+        assert_eq!(min_size([one]), Some(one));
+        assert_eq!(max_size([one]), Some(one));
+        assert_eq!(min_max_size([one]), Some((one, one)));
+
+        let sizes =
+            ["2KiB", "1MiB", "512B", "4096b"].map(|size| ConfigSize::from_str(size).unwrap());
+        assert_eq!(min_size(sizes).unwrap().to_string(), "512B");
+        assert_eq!(max_size(sizes).unwrap().to_string(), "1MiB");
+        let (min, max) = min_max_size(sizes).unwrap();
+        assert_eq!(
+            (min.to_string(), max.to_string()),
+            ("512B".to_owned(), "1MiB".to_owned())
+        );
+    }
+    #[test]
+    pub fn test_size_constraint() {
+        let size = |s: &str| ConfigSize::from_str(s).unwrap();
+        let min = ConfigSizeConstraint::from_str("min:1MiB").unwrap();
+        assert_eq!(min, ConfigSizeConstraint::Min(size("1MiB")));
+        assert!(min.check(size("1MiB")).is_ok());
+        assert_eq!(
+            min.check(size("1KiB")).unwrap_err().to_string(),
+            "1KiB is smaller than the minimum 1MiB"
+        );
+
+        let max = ConfigSizeConstraint::from_str("max:1GiB").unwrap();
+        assert!(max.check(size("1024MiB")).is_ok());
+        assert!(max.check(size("1025MiB")).is_err());
+
+        let exact = ConfigSizeConstraint::from_str("exact:1KiB").unwrap();
+        assert!(exact.check(size("1024B")).is_ok());
+        assert!(exact.check(size("1023B")).is_err());
+
+        let range = ConfigSizeConstraint::from_str("1MiB..1GiB").unwrap();
+        assert!(range.is_range());
+        assert!(range.check(size("1MiB")).is_ok());
+        assert!(range.check(size("1GiB")).is_ok());
+        assert!(range.check(size("1KiB")).is_err());
+        assert!(range.check(size("2GiB")).is_err());
+
+        for constraint in [min, max, exact, range] {
+            assert_eq!(
+                ConfigSizeConstraint::from_str(&constraint.to_string()).unwrap(),
+                constraint
+            );
+        }
+        for invalid in ["1MiB", "between:1MiB", "min:1XiB", "1GiB..1MiB", "min:"] {
+            assert!(
+                ConfigSizeConstraint::from_str(invalid).is_err(),
+                "Input: {:?}",
+                invalid
+            );
+        }
+        let constraint: ConfigSizeConstraint = serde_json::from_str(r#""max:1GiB""#).unwrap();
+        assert_eq!(serde_json::to_string(&constraint).unwrap(), r#""max:1GiB""#);
+    }
+    #[test]
+    pub fn test_size_constraint_error_span() {
+        let error = ConfigSizeConstraint::from_str("min:1XiB").unwrap_err();
+        assert_eq!(error.span(), Some(5..8));
+        let error = ConfigSizeConstraint::from_str("1MiB..1XiB").unwrap_err();
+        assert_eq!(error.span(), Some(7..10));
+        let error = ConfigSizeConstraint::from_str("between:1MiB").unwrap_err();
+        assert_eq!(error.span(), Some(0..7));
+    }
+    #[test]
+    pub fn test_unit_names() {
+        assert_eq!(Unit::Kibibytes.description(), "Kibibyte: 1024 bytes");
+        assert_eq!(Unit::Kibibytes.short_name(), "KiB");
+        assert_eq!(Unit::Kibibytes.long_name(), "kibibyte");
+        assert_eq!(Unit::Kibibytes.long_name_plural(), "kibibytes");
+        for unit in Unit::iter() {
+            assert!(!unit.description().is_empty());
+            assert_eq!(Unit::from_str(unit.short_name()).unwrap(), unit);
+            assert_eq!(Unit::from_long_name(unit.long_name()), Some(unit));
+            assert_eq!(Unit::from_long_name(unit.long_name_plural()), Some(unit));
+        }
+    }
+    #[test]
+    pub fn test_normalize_string() {
+        assert_eq!(ConfigSize::normalize_string("1024 bytes").unwrap(), "1KiB");
+        assert_eq!(ConfigSize::normalize_string("1024KiB").unwrap(), "1MiB");
+        assert_eq!(ConfigSize::normalize_string("1536KiB").unwrap(), "1536KiB");
+        assert_eq!(ConfigSize::normalize_string("2048MiB").unwrap(), "2GiB");
+        assert_eq!(ConfigSize::normalize_string("8192b").unwrap(), "1KiB");
+        assert_eq!(ConfigSize::normalize_string("2049b").unwrap(), "2049b");
+        assert_eq!(
+            ConfigSize::normalize_string("2048 kibibits").unwrap(),
+            "256KiB"
+        );
+        assert_eq!(ConfigSize::normalize_string("0MiB").unwrap(), "0B");
+        assert!(ConfigSize::normalize_string("1XiB").is_err());
+        for input in ["1024 bytes", "3072KiB", "2049b", "1TiB", "0b", "1000"] {
+            let once = ConfigSize::normalize_string(input).unwrap();
+            assert_eq!(ConfigSize::normalize_string(&once).unwrap(), once);
+        }
+    }
+    #[test]
+    pub fn test_long_input() {
+        let start = std::time::Instant::now();
+        let input = "1".repeat(1024 * 1024);
+        let error = ConfigSize::from_str(&input).unwrap_err();
+        assert_eq!(error.span(), Some(0..input.len()));
+        assert!(ConfigSize::from_str(&format!("1{}", "K".repeat(1024 * 1024))).is_err());
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+        let longest = format!("{}B", usize::MAX);
+        assert!(longest.len() <= MAX_INPUT_LEN);
+        assert!(ConfigSize::from_str(&longest).is_ok());
+    }
+    #[test]
+    pub fn test_eq_str() {
+        let size = ConfigSize::new_from_mebibytes(1);
+        assert_eq!(size, "1MiB");
+        assert_eq!(size, "1024KiB");
+        assert!(size == *"1048576B");
+        assert_ne!(size, "2MiB");
+        assert_ne!(size, "not a size");
+    }
+    #[test]
+    pub fn test_parse_or() {
+        let default = ConfigSize::new_from_mebibytes(1);
+        assert_eq!(
+            ConfigSize::parse_or("512KiB", default),
+            ConfigSize::new_from_kibibytes(512)
+        );
+        assert_eq!(ConfigSize::parse_or("512XiB", default), default);
+        assert_eq!(ConfigSize::parse_or("", default), default);
+    }
+    #[cfg(feature = "log")]
+    #[test]
+    pub fn test_parse_or_warn() {
+        let default = ConfigSize::new_from_mebibytes(1);
+        assert_eq!(
+            ConfigSize::parse_or_warn("2KiB", default),
+            ConfigSize::new_from_kibibytes(2)
+        );
+        assert_eq!(ConfigSize::parse_or_warn("2XiB", default), default);
+    }
+    #[test]
+    pub fn test_iter_by() {
+        let sizes: Vec<_> = ConfigSize::iter_by(
+            ConfigSize::new_from_kibibytes(1)..=ConfigSize::new_from_kibibytes(4),
+            ConfigSize::new_from_kibibytes(1),
+        )
+        .collect();
+        assert_eq!(
+            sizes,
+            (1..=4)
+                .map(ConfigSize::new_from_kibibytes)
+                .collect::<Vec<_>>()
+        );
+        let sizes: Vec<_> = ConfigSize::iter_by(
+            ConfigSize::new_from_bytes(0)..=ConfigSize::new_from_kibibytes(1),
+            ConfigSize::from_str("384B").unwrap(),
+        )
+        .map(|size| size.to_string())
+        .collect();
+        assert_eq!(sizes, ["0B", "384B", "768B"]);
+        let empty = ConfigSize::iter_by(
+            ConfigSize::new_from_kibibytes(2)..=ConfigSize::new_from_kibibytes(1),
+            ConfigSize::new_from_bytes(1),
+        );
+        assert_eq!(empty.count(), 0);
+        let near_max = ConfigSize::new_from_bytes(usize::MAX - 1);
+        let sizes = ConfigSize::iter_by(
+            near_max..=ConfigSize::new_from_bytes(usize::MAX),
+            ConfigSize::new_from_bytes(2),
+        );
+        assert_eq!(sizes.count(), 1);
+    }
+    #[test]
+    #[should_panic]
+    pub fn test_iter_by_zero_step() {
+        let _ = ConfigSize::iter_by(ConfigSize::ZERO..=ConfigSize::ZERO, ConfigSize::ZERO);
+    }
+    #[test]
+    pub fn test_read_from_file() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "  536870912  ").unwrap();
+        let size = ConfigSize::read_from_file(file.path()).unwrap();
+        assert_eq!(size, ConfigSize::new_from_mebibytes(512));
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "512MiB").unwrap();
+        assert_eq!(
+            ConfigSize::read_from_file(file.path()).unwrap(),
+            ConfigSize::new_from_mebibytes(512)
+        );
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "max").unwrap();
+        assert!(matches!(
+            ConfigSize::read_from_file(file.path()),
+            Err(ConfigSizeFileError::InvalidSize(_))
+        ));
+        let directory = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            ConfigSize::read_from_file(directory.path().join("missing")),
+            Err(ConfigSizeFileError::Io(_))
+        ));
+    }
+    #[test]
+    pub fn test_from_long_name() {
+        assert_eq!(Unit::from_long_name("mebibytes"), Some(Unit::Mebibytes));
+        assert_eq!(Unit::from_long_name("MEBIBYTE"), Some(Unit::Mebibytes));
+        assert_eq!(Unit::from_long_name("kibibits"), Some(Unit::Kibibits));
+        assert_eq!(Unit::from_long_name("megabytes"), None);
+        assert_eq!(Unit::from_long_name("MiB"), None);
+        for unit in Unit::iter() {
+            let uppercase = unit.long_name_plural().to_uppercase();
+            assert_eq!(Unit::from_long_name(&uppercase), Some(unit));
+        }
+    }
+    #[test]
+    pub fn test_error_accessors() {
+        let error = ConfigSize::from_str("10XiB").unwrap_err();
+        assert_eq!(error.context(), "Unknown unit");
+        assert!(error.source_error().is_none());
+        assert_eq!(error.span(), Some(2..5));
+        assert_eq!(error.input(), Some("10XiB"));
+
+        let error = ConfigSize::from_str("99999999999999999999999B").unwrap_err();
+        assert_eq!(error.context(), "Invalid Size");
+        assert!(error.source_error().is_some());
+
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(ConfigSize::from_str(&long).unwrap_err().input(), None);
+        assert_eq!(InvalidSizeError::from("Test").input(), None);
+    }
+    #[test]
+    pub fn test_unit_index() {
+        assert_eq!(Unit::count(), 8);
+        assert_eq!(Unit::all().len(), Unit::count());
+        assert_eq!(Unit::all(), Unit::iter().collect::<Vec<_>>());
+        for (index, unit) in Unit::iter().enumerate() {
+            assert_eq!(unit.index(), index);
+            assert_eq!(Unit::from_index(index), Some(unit));
+        }
+        assert_eq!(Unit::Kibibytes.index(), 1);
+        assert_eq!(Unit::from_index(Unit::count()), None);
+    }
+    #[test]
+    pub fn test_unit_ord() {
+        assert!(Unit::Bits < Unit::Bytes);
+        assert!(Unit::Mebibits < Unit::Mebibytes);
+        assert!(Unit::Mebibytes < Unit::Gibibytes);
+        let mut units = Unit::all().to_vec();
+        units.sort();
+        assert_eq!(
+            units,
+            [
+                Unit::Bits,
+                Unit::Bytes,
+                Unit::Kibibits,
+                Unit::Kibibytes,
+                Unit::Mebibits,
+                Unit::Mebibytes,
+                Unit::Gibibytes,
+                Unit::Tebibytes,
+            ]
+        );
+        assert!(units.windows(2).all(|pair| pair[0].bits() < pair[1].bits()));
+    }
+    #[test]
+    pub fn test_from_usize_units() {
+        for (bytes, parts) in [
+            (1500, (1500, Unit::Bytes)),
+            (2048, (2, Unit::Kibibytes)),
+            (1048576, (1, Unit::Mebibytes)),
+        ] {
+            let size = ConfigSize::from(bytes);
+            assert_eq!(size.as_parts(), parts);
+            assert_eq!(size.get_as_bytes(), bytes);
+        }
+        assert_eq!(
+            ConfigSize::new_from_bytes(1500).as_parts(),
+            (1500, Unit::Bytes)
+        );
+    }
+    #[test]
+    pub fn test_short_constructors() {
+        assert_eq!(
+            ConfigSize::from_b(10).as_parts(),
+            ConfigSize::new_from_bytes(10).as_parts()
+        );
+        assert_eq!(
+            ConfigSize::from_kib(10).as_parts(),
+            ConfigSize::new_from_kibibytes(10).as_parts()
+        );
+        assert_eq!(
+            ConfigSize::from_mib(10).as_parts(),
+            ConfigSize::new_from_mebibytes(10).as_parts()
+        );
+        assert_eq!(ConfigSize::from_gib(2).to_string(), "2GiB");
+        assert_eq!(ConfigSize::from_tib(1).to_string(), "1TiB");
+        assert_eq!(ConfigSize::from_gib(1), ConfigSize::from_mib(1024));
+    }
+    #[test]
+    pub fn test_percent_of_quota() {
+        let used = ConfigSize::from_str("750MiB").unwrap();
+        let total = ConfigSize::from_str("1GiB").unwrap();
+        assert_eq!(used.percent_of(&total), 73.2421875);
+        assert_eq!(used.percent_of(&ConfigSize::ZERO), 0.0);
+        assert_eq!(ConfigSize::ZERO.percent_of(&ConfigSize::ZERO), 0.0);
+        assert_eq!(used.percent_of(&ConfigSize::from_str("0MiB").unwrap()), 0.0);
+    }
+}
+
+#[cfg(all(test, feature = "digestible"))]
+mod digestible_tests {
+    use super::*;
+    use digestible::{byteorder::BigEndian, Digestible};
+
+    fn digest(size: &ConfigSize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        size.digest::<BigEndian, _>(&mut bytes);
+        bytes
+    }
+    #[test]
+    pub fn test_deterministic() {
+        let first = digest(&ConfigSize::new_from_mebibytes(1));
+        let second = digest(&ConfigSize::new_from_mebibytes(1));
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+        assert_ne!(first, digest(&ConfigSize::new_from_mebibytes(2)));
+    }
+    #[test]
+    pub fn test_unit_is_digested() {
+        let kibibytes = ConfigSize::new_from_kibibytes(1024);
+        let mebibytes = ConfigSize::new_from_mebibytes(1);
+        assert_eq!(kibibytes.get_as_bytes(), mebibytes.get_as_bytes());
+        assert_ne!(digest(&kibibytes), digest(&mebibytes));
+    }
 }
@@ -0,0 +1,148 @@
+//! [sqlx](https://docs.rs/sqlx) support for the config types
+//!
+//! Both types are stored as a `BIGINT`. Making them usable with any database that supports `i64`.
+//!
+//! | Type             | Column Type | Stored As          |
+//! |------------------|-------------|--------------------|
+//! | `ConfigSize`     | `BIGINT`    | Total bytes        |
+//! | `ConfigDuration` | `BIGINT`    | Total milliseconds |
+//!
+//! The unit is not stored. Values read from the database use the largest unit that exactly fits
+//! for sizes and milliseconds for durations.
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Type};
+
+use crate::size_config::ConfigSize;
+
+fn size_to_i64(size: &ConfigSize) -> Result<i64, BoxDynError> {
+    Ok(i64::try_from(size.get_as_bytes())?)
+}
+fn size_from_i64(bytes: i64) -> Result<ConfigSize, BoxDynError> {
+    Ok(ConfigSize::from(usize::try_from(bytes)?))
+}
+
+impl<DB: Database> Type<DB> for ConfigSize
+where
+    i64: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+    }
+}
+impl<'q, DB: Database> Encode<'q, DB> for ConfigSize
+where
+    i64: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        size_to_i64(self)?.encode_by_ref(buf)
+    }
+}
+impl<'r, DB: Database> Decode<'r, DB> for ConfigSize
+where
+    i64: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        size_from_i64(<i64 as Decode<DB>>::decode(value)?)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod duration {
+    use chrono::Duration;
+    use sqlx::decode::Decode;
+    use sqlx::encode::{Encode, IsNull};
+    use sqlx::error::BoxDynError;
+    use sqlx::{Database, Type};
+
+    use crate::chrono_types::duration::ConfigDuration;
+
+    pub(super) fn duration_from_i64(milliseconds: i64) -> Result<ConfigDuration, BoxDynError> {
+        Duration::try_milliseconds(milliseconds)
+            .map(ConfigDuration::from)
+            .ok_or_else(|| "Duration is out of range".into())
+    }
+
+    impl<DB: Database> Type<DB> for ConfigDuration
+    where
+        i64: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <i64 as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <i64 as Type<DB>>::compatible(ty)
+        }
+    }
+    impl<'q, DB: Database> Encode<'q, DB> for ConfigDuration
+    where
+        i64: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            self.num_milliseconds().encode_by_ref(buf)
+        }
+    }
+    impl<'r, DB: Database> Decode<'r, DB> for ConfigDuration
+    where
+        i64: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            duration_from_i64(<i64 as Decode<DB>>::decode(value)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+    use sqlx::Postgres;
+
+    fn encode<'q, T: Encode<'q, Postgres>>(value: T) -> Vec<u8> {
+        let mut buffer = PgArgumentBuffer::default();
+        let is_null = value.encode_by_ref(&mut buffer).unwrap();
+        assert!(!is_null.is_null());
+        buffer.to_vec()
+    }
+    #[test]
+    pub fn test_size() {
+        assert_eq!(
+            <ConfigSize as Type<Postgres>>::type_info(),
+            PgTypeInfo::with_name("INT8")
+        );
+        let size = ConfigSize::new_from_mebibytes(5);
+        assert_eq!(encode(size), encode(5i64 * 1024 * 1024));
+
+        let bytes = size_to_i64(&size).unwrap();
+        assert_eq!(size_from_i64(bytes).unwrap(), size);
+        assert!(size_from_i64(-1).is_err());
+        assert!(size_to_i64(&ConfigSize::MAX).is_err());
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration() {
+        use crate::chrono_types::duration::ConfigDuration;
+        use std::str::FromStr;
+        assert_eq!(
+            <ConfigDuration as Type<Postgres>>::type_info(),
+            PgTypeInfo::with_name("INT8")
+        );
+        let duration = ConfigDuration::from_str("90s").unwrap();
+        assert_eq!(encode(duration), encode(90_000i64));
+
+        let decoded = duration::duration_from_i64(duration.num_milliseconds()).unwrap();
+        assert_eq!(decoded.duration, duration.duration);
+    }
+}
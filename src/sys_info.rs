@@ -0,0 +1,106 @@
+//! Reading memory and disk space from the OS. For configs that are relative to the machine
+//!
+//! Memory is read from `/proc/meminfo` so is only supported on Linux.
+//! Disk space uses `statvfs` and is supported on all unix targets
+//!
+//! ```rust,no_run
+//! use tuxs_config_types::size_config::ConfigSize;
+//! use tuxs_config_types::sys_info::available_memory;
+//!
+//! let cache = ConfigSize::new_from_mebibytes(512);
+//! let cache = cache.min(available_memory().unwrap());
+//! ```
+use std::error::Error;
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::size_config::ConfigSize;
+
+type AnyError = Box<dyn Error + Send + Sync + 'static>;
+#[derive(Debug, Error)]
+#[error("{0}: {1:?}")]
+pub struct OsError(&'static str, Option<AnyError>);
+
+impl From<(&'static str, AnyError)> for OsError {
+    fn from(value: (&'static str, AnyError)) -> Self {
+        Self(value.0, Some(value.1))
+    }
+}
+impl From<&'static str> for OsError {
+    fn from(value: &'static str) -> Self {
+        Self(value, None)
+    }
+}
+/// The memory available for new processes. `MemAvailable` in `/proc/meminfo`
+pub fn available_memory() -> Result<ConfigSize, OsError> {
+    read_meminfo("MemAvailable")
+}
+/// The total physical memory. `MemTotal` in `/proc/meminfo`
+pub fn total_memory() -> Result<ConfigSize, OsError> {
+    read_meminfo("MemTotal")
+}
+/// The total size of the file system that `path` is on
+pub fn total_disk_space(path: impl AsRef<Path>) -> Result<ConfigSize, OsError> {
+    #[cfg(unix)]
+    {
+        let stat = nix::sys::statvfs::statvfs(path.as_ref())
+            .map_err(|err| OsError::from(("Unable to read the file system", err.into())))?;
+        // The field types are smaller than u64 on some targets
+        #[allow(clippy::useless_conversion)]
+        let bytes = u64::from(stat.blocks()).saturating_mul(u64::from(stat.fragment_size()));
+        Ok(ConfigSize::from(
+            usize::try_from(bytes).unwrap_or(usize::MAX),
+        ))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Err(OsError::from(
+            "Reading disk space is only supported on unix",
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_meminfo(field: &str) -> Result<ConfigSize, OsError> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo")
+        .map_err(|err| OsError::from(("Unable to read /proc/meminfo", err.into())))?;
+    // Lines look like `MemTotal:       16318196 kB`
+    let kibibytes = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .ok_or(OsError::from("Field is missing from /proc/meminfo"))?
+        .trim()
+        .parse::<usize>()
+        .map_err(|err| OsError::from(("Invalid value in /proc/meminfo", err.into())))?;
+    Ok(ConfigSize::from(kibibytes.saturating_mul(1024)))
+}
+#[cfg(not(target_os = "linux"))]
+fn read_meminfo(_field: &str) -> Result<ConfigSize, OsError> {
+    Err(OsError::from("Reading memory is only supported on Linux"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    pub fn test_memory() {
+        let total = total_memory().unwrap();
+        let available = available_memory().unwrap();
+        assert!(!total.is_zero());
+        assert!(!available.is_zero());
+        assert!(available <= total);
+    }
+    #[cfg(unix)]
+    #[test]
+    pub fn test_disk_space() {
+        assert!(!total_disk_space(env!("CARGO_MANIFEST_DIR"))
+            .unwrap()
+            .is_zero());
+        assert!(total_disk_space("/does/not/exist").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Ready made values for tests. Enable the `test-helpers` feature to use them in your own tests
+use crate::size_config::{ConfigSize, Unit};
+
+pub const ONE_KIB: ConfigSize = ConfigSize {
+    size: 1,
+    unit: Unit::Kibibytes,
+};
+pub const ONE_MIB: ConfigSize = ConfigSize {
+    size: 1,
+    unit: Unit::Mebibytes,
+};
+pub const ONE_GIB: ConfigSize = ConfigSize {
+    size: 1,
+    unit: Unit::Gibibytes,
+};
+#[cfg(feature = "chrono")]
+pub use duration::*;
+#[cfg(feature = "chrono")]
+mod duration {
+    use crate::chrono_types::duration::{ConfigDuration, Unit};
+    use chrono::Duration;
+
+    pub const ONE_SECOND: ConfigDuration = ConfigDuration {
+        duration: Duration::seconds(1),
+        unit: Unit::Seconds,
+    };
+    pub const ONE_MINUTE: ConfigDuration = ConfigDuration {
+        duration: Duration::minutes(1),
+        unit: Unit::Minutes,
+    };
+    pub const ONE_HOUR: ConfigDuration = ConfigDuration {
+        duration: Duration::hours(1),
+        unit: Unit::Hours,
+    };
+}
+/// A size picked from `seed`. The same seed always gives the same size
+///
+/// The size is below 1024 in any unit
+pub fn arbitrary_size(seed: u64) -> ConfigSize {
+    // SplitMix64. Enough to spread similar seeds apart
+    let mut value = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^= value >> 31;
+    let unit = Unit::all()[(value % Unit::count() as u64) as usize];
+    ConfigSize {
+        size: ((value >> 32) % 1024) as usize,
+        unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_constants() {
+        assert_eq!(ONE_KIB, ConfigSize::from_str("1KiB").unwrap());
+        assert_eq!(ONE_MIB, ConfigSize::from_str("1024KiB").unwrap());
+        assert_eq!(ONE_GIB.to_string(), "1GiB");
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration_constants() {
+        assert_eq!(ONE_SECOND.to_string(), "1s");
+        assert_eq!(ONE_MINUTE, "60s");
+        assert_eq!(ONE_HOUR.to_string(), "1h");
+    }
+    #[test]
+    pub fn test_arbitrary_size() {
+        assert_eq!(arbitrary_size(42), arbitrary_size(42));
+        assert_eq!(arbitrary_size(42).unit, arbitrary_size(42).unit);
+        let sizes: Vec<_> = (0..64).map(arbitrary_size).collect();
+        assert!(sizes.iter().all(|size| size.size < 1024));
+        assert!(sizes.windows(2).any(|pair| pair[0] != pair[1]));
+        assert!(sizes.iter().any(|size| size.unit != sizes[0].unit));
+        for size in sizes {
+            assert_eq!(ConfigSize::from_str(&size.to_string()).unwrap(), size);
+        }
+    }
+}
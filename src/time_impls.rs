@@ -0,0 +1,68 @@
+//! Conversions between [ConfigDuration] and [time::Duration]
+//!
+//! `time` can represent much longer durations than `chrono` and down to the nanosecond.
+//! Converting from `time` truncates to the millisecond towards zero and saturates at [ConfigDuration::MAX] and [ConfigDuration::MIN].
+//! Converting to `time` never loses anything
+use crate::chrono_types::duration::ConfigDuration;
+
+/// Keeps the sign. Never overflows as every `chrono` duration fits in `time`
+impl From<ConfigDuration> for time::Duration {
+    fn from(value: ConfigDuration) -> Self {
+        time::Duration::milliseconds(value.num_milliseconds())
+    }
+}
+/// Truncates to the millisecond towards zero. Saturates when out of range for `chrono`
+impl From<time::Duration> for ConfigDuration {
+    fn from(value: time::Duration) -> Self {
+        let milliseconds = value.whole_milliseconds();
+        if milliseconds > ConfigDuration::MAX.num_milliseconds() as i128 {
+            ConfigDuration::MAX
+        } else if milliseconds < ConfigDuration::MIN.num_milliseconds() as i128 {
+            ConfigDuration::MIN
+        } else {
+            ConfigDuration::in_largest_exact_unit(chrono::Duration::milliseconds(
+                milliseconds as i64,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_round_trip() {
+        for input in ["0ms", "1500ms", "30s", "-90m", "2h", "-7d"] {
+            let duration = ConfigDuration::from_str(input).unwrap();
+            let time = time::Duration::from(duration);
+            assert_eq!(
+                time.whole_milliseconds(),
+                duration.num_milliseconds() as i128
+            );
+            assert_eq!(ConfigDuration::from(time), duration);
+        }
+        assert_eq!(
+            ConfigDuration::from(time::Duration::minutes(90)).to_string(),
+            "90m"
+        );
+        assert_eq!(
+            time::Duration::from(ConfigDuration::MIN).whole_milliseconds(),
+            ConfigDuration::MIN.num_milliseconds() as i128
+        );
+    }
+    #[test]
+    pub fn test_truncate_and_saturate() {
+        let duration = ConfigDuration::from(time::Duration::microseconds(-1_999));
+        assert_eq!(duration.num_milliseconds(), -1);
+        assert_eq!(
+            ConfigDuration::from(time::Duration::MAX),
+            ConfigDuration::MAX
+        );
+        assert_eq!(
+            ConfigDuration::from(time::Duration::MIN),
+            ConfigDuration::MIN
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Using [ConfigDuration] directly with [tokio::time]
+//!
+//! Negative durations are treated as zero. So `sleep` returns right away
+//!
+//! ```rust,no_run
+//! use tuxs_config_types::chrono_types::duration::ConfigDuration;
+//! use tuxs_config_types::tokio_ext::*;
+//! # async fn example() {
+//! let poll_interval = ConfigDuration::parse("30s").unwrap();
+//! let mut interval = poll_interval.interval();
+//! loop {
+//!     interval.tick().await;
+//! }
+//! # }
+//! ```
+use std::future::Future;
+
+use tokio::time::{Interval, Sleep, Timeout};
+
+use crate::chrono_types::duration::ConfigDuration;
+
+/// Wrappers over [tokio::time] that take a [ConfigDuration]
+pub trait ConfigDurationTokioExt {
+    /// The same as [tokio::time::sleep]
+    fn sleep(self) -> Sleep;
+    /// The same as [tokio::time::interval]
+    ///
+    /// # Panics
+    /// If the duration is zero or negative. The same as [tokio::time::interval]
+    fn interval(self) -> Interval;
+    /// The same as [tokio::time::timeout]
+    fn timeout<F: Future>(self, future: F) -> Timeout<F>;
+}
+impl ConfigDurationTokioExt for ConfigDuration {
+    fn sleep(self) -> Sleep {
+        tokio::time::sleep(to_std(self))
+    }
+    fn interval(self) -> Interval {
+        tokio::time::interval(to_std(self))
+    }
+    fn timeout<F: Future>(self, future: F) -> Timeout<F> {
+        tokio::time::timeout(to_std(self), future)
+    }
+}
+/// Negative durations become zero
+fn to_std(duration: ConfigDuration) -> std::time::Duration {
+    duration.to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    pub async fn test_sleep() {
+        let start = Instant::now();
+        ConfigDuration::parse("20ms").unwrap().sleep().await;
+        assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+        ConfigDuration::parse("-1s").unwrap().sleep().await;
+    }
+    #[tokio::test]
+    pub async fn test_interval() {
+        let mut interval = ConfigDuration::parse("10ms").unwrap().interval();
+        let start = Instant::now();
+        interval.tick().await;
+        interval.tick().await;
+        assert!(start.elapsed() >= std::time::Duration::from_millis(10));
+    }
+    #[tokio::test]
+    pub async fn test_timeout() {
+        let timeout = ConfigDuration::parse("10ms").unwrap();
+        assert_eq!(timeout.timeout(async { 5 }).await.unwrap(), 5);
+        let never = std::future::pending::<()>();
+        assert!(timeout.timeout(never).await.is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Validating config values after they are parsed
+//!
+//! ```rust
+//! use tuxs_config_types::memory_limit::ConfigMemoryLimit;
+//! use tuxs_config_types::validation::validate_all;
+//! use std::str::FromStr;
+//!
+//! let limits = [
+//!     ConfigMemoryLimit::from_str("128MiB/256MiB").unwrap(),
+//!     ConfigMemoryLimit::from_str("1MiB").unwrap(),
+//! ];
+//! assert!(validate_all(&limits).is_ok());
+//! ```
+use thiserror::Error;
+
+use crate::memory_limit::ConfigMemoryLimit;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("{0}")]
+pub struct ConfigValidationError(pub String);
+impl From<&str> for ConfigValidationError {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+impl From<String> for ConfigValidationError {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+/// A config value that can check itself for problems
+pub trait ConfigValidate {
+    fn validate(&self) -> Result<(), ConfigValidationError>;
+}
+impl<T: ConfigValidate> ConfigValidate for Option<T> {
+    fn validate(&self) -> Result<(), ConfigValidationError> {
+        match self {
+            Some(value) => value.validate(),
+            None => Ok(()),
+        }
+    }
+}
+impl ConfigValidate for ConfigMemoryLimit {
+    fn validate(&self) -> Result<(), ConfigValidationError> {
+        match self.soft() {
+            Some(soft) if soft > self.hard() => Err(ConfigValidationError(format!(
+                "Soft limit {} is greater than the hard limit {}",
+                soft,
+                self.hard()
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+/// Validates every value. Returning all the errors found
+pub fn validate_all<T: ConfigValidate>(values: &[T]) -> Result<(), Vec<ConfigValidationError>> {
+    let errors: Vec<_> = values
+        .iter()
+        .filter_map(|value| value.validate().err())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Even(u32);
+    impl ConfigValidate for Even {
+        fn validate(&self) -> Result<(), ConfigValidationError> {
+            if self.0 % 2 == 0 {
+                Ok(())
+            } else {
+                Err(ConfigValidationError(format!("{} is not even", self.0)))
+            }
+        }
+    }
+    #[test]
+    pub fn test_validate_all() {
+        assert!(validate_all(&[Even(2), Even(4)]).is_ok());
+        let errors = validate_all(&[Even(1), Even(2), Even(3)]).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ConfigValidationError::from("1 is not even"),
+                ConfigValidationError::from("3 is not even")
+            ]
+        );
+        assert!(validate_all(&[None, Some(Even(2))]).is_ok());
+        assert!(validate_all::<Even>(&[]).is_ok());
+    }
+}
@@ -0,0 +1,272 @@
+//! Custom validators for the [validator](https://docs.rs/validator) crate
+//!
+//! ```rust
+//! use tuxs_config_types::size_config::ConfigSize;
+//! use tuxs_config_types::validators::{max_size, max_size_1gib, non_zero_size};
+//! use validator::{Validate, ValidationError};
+//! use std::str::FromStr;
+//!
+//! fn max_upload(size: &ConfigSize) -> Result<(), ValidationError> {
+//!     max_size(ConfigSize::from_str("100MiB").unwrap())(size)
+//! }
+//! #[derive(Validate)]
+//! struct Config {
+//!     #[validate(custom(function = "non_zero_size"), custom(function = "max_size_1gib"))]
+//!     cache: ConfigSize,
+//!     #[validate(custom(function = "max_upload"))]
+//!     max_upload: ConfigSize,
+//! }
+//! let config = Config {
+//!     cache: ConfigSize::from_str("512MiB").unwrap(),
+//!     max_upload: ConfigSize::from_str("1GiB").unwrap(),
+//! };
+//! assert!(config.validate().is_err());
+//! ```
+//!
+//! Sizes and durations also work with `#[validate(range(...))]`. The bounds must be constants of the same type
+//! ```rust
+//! use tuxs_config_types::size_config::{ConfigSize, Unit};
+//! use validator::Validate;
+//!
+//! const MAX_UPLOAD: ConfigSize = ConfigSize { size: 100, unit: Unit::Mebibytes };
+//! #[derive(Validate)]
+//! struct Config {
+//!     #[validate(range(max = MAX_UPLOAD))]
+//!     max_upload: ConfigSize,
+//! }
+//! let config = Config { max_upload: ConfigSize::new_from_mebibytes(101) };
+//! assert!(config.validate().is_err());
+//! ```
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use validator::{ValidateRange, ValidationError};
+
+use crate::size_config::{ConfigSize, Unit};
+
+/// Allows `#[validate(range(...))]`. Compared by the total number of bits
+impl ValidateRange<ConfigSize> for ConfigSize {
+    fn greater_than(&self, max: ConfigSize) -> Option<bool> {
+        Some(*self > max)
+    }
+    fn less_than(&self, min: ConfigSize) -> Option<bool> {
+        Some(*self < min)
+    }
+}
+
+fn limit_error(code: &'static str, message: &'static str, limit: impl Display) -> ValidationError {
+    let limit = limit.to_string();
+    let mut error =
+        ValidationError::new(code).with_message(Cow::Owned(format!("{} {}", message, limit)));
+    error.add_param(Cow::Borrowed("limit"), &limit);
+    error
+}
+
+/// Errors if the size is smaller than `limit`
+pub fn min_size(limit: ConfigSize) -> impl Fn(&ConfigSize) -> Result<(), ValidationError> {
+    move |size| {
+        if *size < limit {
+            return Err(limit_error("min_size", "Size must be at least", limit));
+        }
+        Ok(())
+    }
+}
+/// Errors if the size is larger than `limit`
+pub fn max_size(limit: ConfigSize) -> impl Fn(&ConfigSize) -> Result<(), ValidationError> {
+    move |size| {
+        if *size > limit {
+            return Err(limit_error("max_size", "Size must be at most", limit));
+        }
+        Ok(())
+    }
+}
+/// Errors if the size is larger than 1GiB
+pub fn max_size_1gib(size: &ConfigSize) -> Result<(), ValidationError> {
+    max_size(ConfigSize {
+        size: 1,
+        unit: Unit::Gibibytes,
+    })(size)
+}
+/// Errors if the size is zero
+pub fn non_zero_size(size: &ConfigSize) -> Result<(), ValidationError> {
+    if size.is_zero() {
+        return Err(ValidationError::new("non_zero_size")
+            .with_message(Cow::Borrowed("Size must not be zero")));
+    }
+    Ok(())
+}
+#[cfg(feature = "chrono")]
+mod duration {
+    use super::*;
+    use crate::chrono_types::duration::ConfigDuration;
+
+    /// Allows `#[validate(range(...))]`. Compared by the full duration
+    impl ValidateRange<ConfigDuration> for ConfigDuration {
+        fn greater_than(&self, max: ConfigDuration) -> Option<bool> {
+            Some(*self > max)
+        }
+        fn less_than(&self, min: ConfigDuration) -> Option<bool> {
+            Some(*self < min)
+        }
+    }
+
+    /// Errors if the duration is shorter than `limit`
+    pub fn min_duration(
+        limit: ConfigDuration,
+    ) -> impl Fn(&ConfigDuration) -> Result<(), ValidationError> {
+        move |duration| {
+            if *duration < limit {
+                return Err(limit_error(
+                    "min_duration",
+                    "Duration must be at least",
+                    limit,
+                ));
+            }
+            Ok(())
+        }
+    }
+    /// Errors if the duration is longer than `limit`
+    pub fn max_duration(
+        limit: ConfigDuration,
+    ) -> impl Fn(&ConfigDuration) -> Result<(), ValidationError> {
+        move |duration| {
+            if *duration > limit {
+                return Err(limit_error(
+                    "max_duration",
+                    "Duration must be at most",
+                    limit,
+                ));
+            }
+            Ok(())
+        }
+    }
+    /// Errors if the duration is zero
+    pub fn non_zero_duration(duration: &ConfigDuration) -> Result<(), ValidationError> {
+        if duration.is_zero() {
+            return Err(ValidationError::new("non_zero_duration")
+                .with_message(Cow::Borrowed("Duration must not be zero")));
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "chrono")]
+pub use duration::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{ONE_GIB, ONE_KIB, ONE_MIB};
+    use std::str::FromStr;
+    use validator::Validate;
+
+    #[test]
+    pub fn test_size_validators() {
+        let limit = ONE_MIB;
+        let small = ConfigSize::from_str("512KiB").unwrap();
+        let large = ConfigSize::from_str("2MiB").unwrap();
+        assert!(max_size(limit)(&small).is_ok());
+        assert!(max_size(limit)(&limit).is_ok());
+        let error = max_size(limit)(&large).unwrap_err();
+        assert_eq!(error.code, "max_size");
+        assert_eq!(error.message.unwrap(), "Size must be at most 1MiB");
+        assert!(min_size(limit)(&small).is_err());
+        assert!(min_size(limit)(&large).is_ok());
+
+        assert!(max_size_1gib(&ONE_GIB).is_ok());
+        assert!(max_size_1gib(&ConfigSize::from_str("1025MiB").unwrap()).is_err());
+        assert!(non_zero_size(&ConfigSize::ZERO).is_err());
+        assert!(non_zero_size(&small).is_ok());
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_duration_validators() {
+        use crate::chrono_types::duration::ConfigDuration;
+        use crate::test_helpers::{ONE_HOUR, ONE_MINUTE};
+        let limit = ONE_MINUTE;
+        let short = ConfigDuration::from_str("30s").unwrap();
+        let long = ONE_HOUR;
+        assert!(max_duration(limit)(&short).is_ok());
+        assert!(max_duration(limit)(&long).is_err());
+        assert!(min_duration(limit)(&short).is_err());
+        assert!(min_duration(limit)(&limit).is_ok());
+        assert!(non_zero_duration(&ConfigDuration::ZERO).is_err());
+        assert!(non_zero_duration(&short).is_ok());
+    }
+    #[test]
+    pub fn test_derive() {
+        fn at_least_1kib(size: &ConfigSize) -> Result<(), ValidationError> {
+            min_size(ONE_KIB)(size)
+        }
+        #[derive(Validate)]
+        struct Config {
+            #[validate(custom(function = "non_zero_size"), custom(function = "max_size_1gib"))]
+            cache: ConfigSize,
+            #[validate(custom(function = "at_least_1kib"))]
+            buffer: Option<ConfigSize>,
+        }
+        let valid = Config {
+            cache: ConfigSize::from_str("512MiB").unwrap(),
+            buffer: None,
+        };
+        assert!(valid.validate().is_ok());
+        let invalid = Config {
+            cache: ConfigSize::ZERO,
+            buffer: Some(ConfigSize::new_from_bytes(10)),
+        };
+        let errors = invalid.validate().unwrap_err();
+        let fields = errors.field_errors();
+        assert_eq!(fields["cache"][0].code, "non_zero_size");
+        assert_eq!(fields["buffer"][0].code, "min_size");
+    }
+    #[test]
+    pub fn test_derive_range() {
+        #[derive(Validate)]
+        struct Config {
+            #[validate(range(min = ONE_KIB, max = ONE_MIB))]
+            buffer: ConfigSize,
+            #[validate(range(exclusive_max = ONE_MIB))]
+            cache: Option<ConfigSize>,
+        }
+        let valid = Config {
+            buffer: ConfigSize::from_str("1024KiB").unwrap(),
+            cache: None,
+        };
+        assert!(valid.validate().is_ok());
+        let invalid = Config {
+            buffer: ConfigSize::from_str("2MiB").unwrap(),
+            cache: Some(ConfigSize::from_str("1048576B").unwrap()),
+        };
+        let errors = invalid.validate().unwrap_err();
+        let fields = errors.field_errors();
+        assert_eq!(fields["buffer"][0].code, "range");
+        assert_eq!(fields["cache"][0].code, "range");
+        let too_small = Config {
+            buffer: ConfigSize::from_str("1023B").unwrap(),
+            cache: None,
+        };
+        assert!(too_small.validate().is_err());
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    pub fn test_derive_duration_range() {
+        use crate::chrono_types::duration::{ConfigDuration, Unit};
+        use crate::test_helpers::ONE_MINUTE;
+        const MAX: ConfigDuration = ConfigDuration {
+            duration: chrono::Duration::seconds(30),
+            unit: Unit::Seconds,
+        };
+        #[derive(Validate)]
+        struct Config {
+            #[validate(range(max = MAX))]
+            timeout: ConfigDuration,
+        }
+        let valid = Config {
+            timeout: ConfigDuration::from_str("30000ms").unwrap(),
+        };
+        assert!(valid.validate().is_ok());
+        let invalid = Config {
+            timeout: ONE_MINUTE,
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
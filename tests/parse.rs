@@ -0,0 +1,17 @@
+//! The inherent parse functions work without `std::str::FromStr` in scope
+use tuxs_config_types::size_config::{ConfigSize, Unit};
+
+#[test]
+pub fn test_size() {
+    let size = ConfigSize::parse("5MiB").unwrap();
+    assert_eq!(size.as_parts(), (5, Unit::Mebibytes));
+    assert!(ConfigSize::parse("5XB").is_err());
+}
+#[cfg(feature = "chrono")]
+#[test]
+pub fn test_duration() {
+    use tuxs_config_types::chrono_types::duration::{ConfigDuration, Unit};
+    let duration = ConfigDuration::parse("30s").unwrap();
+    assert_eq!(duration.as_parts(), (30, Unit::Seconds));
+    assert!(ConfigDuration::parse("30x").is_err());
+}
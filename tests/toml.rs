@@ -0,0 +1,118 @@
+//! Deserializing the config types from real TOML documents
+use serde::{Deserialize, Serialize};
+use tuxs_config_types::size_config::{ConfigSize, ConfigSizeLimit, Unit};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SizeConfig {
+    max_upload: ConfigSize,
+    cache: ConfigSize,
+    limit: ConfigSizeLimit,
+}
+
+#[test]
+pub fn test_size() {
+    let config: SizeConfig = toml::from_str(
+        r#"
+            max_upload = "10MiB"
+            cache = 1024
+            limit = "unlimited"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.max_upload.as_parts(), (10, Unit::Mebibytes));
+    assert_eq!(config.cache.get_as_bytes(), 1024);
+    assert_eq!(config.limit, ConfigSizeLimit::Unlimited);
+
+    let serialized = toml::to_string(&config).unwrap();
+    assert!(serialized.contains(r#"max_upload = "10MiB""#));
+    assert!(serialized.contains(r#"cache = "1024B""#));
+    assert_eq!(toml::from_str::<SizeConfig>(&serialized).unwrap(), config);
+}
+
+#[test]
+pub fn test_invalid() {
+    let result = toml::from_str::<SizeConfig>(
+        r#"
+            max_upload = "10XiB"
+            cache = 1024
+            limit = "unlimited"
+        "#,
+    );
+    assert!(result.is_err());
+    let result = toml::from_str::<SizeConfig>(
+        r#"
+            max_upload = 1.5
+            cache = 1024
+            limit = "unlimited"
+        "#,
+    );
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+pub fn test_duration() {
+    use std::str::FromStr;
+    use tuxs_config_types::chrono_types::duration::ConfigDuration;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct DurationConfig {
+        timeout: ConfigDuration,
+        interval: ConfigDuration,
+        #[serde(default)]
+        retry_after: Option<ConfigDuration>,
+    }
+    let config: DurationConfig = toml::from_str(
+        r#"
+            timeout = "30s"
+            interval = 500
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.timeout, ConfigDuration::from_str("30s").unwrap());
+    assert_eq!(config.interval.num_milliseconds(), 500);
+    assert_eq!(config.retry_after, None);
+
+    let serialized = toml::to_string(&config).unwrap();
+    assert!(serialized.contains(r#"timeout = "30s""#));
+    assert_eq!(
+        toml::from_str::<DurationConfig>(&serialized).unwrap(),
+        config
+    );
+    let negative = toml::from_str::<DurationConfig>("timeout = -5\ninterval = 1").unwrap();
+    assert_eq!(negative.timeout.num_milliseconds(), -5);
+    assert!(toml::from_str::<DurationConfig>("timeout = \"5x\"\ninterval = 1").is_err());
+}
+#[test]
+pub fn test_optional_size() {
+    use tuxs_config_types::size_config::deserialize_optional_size;
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        #[serde(default, deserialize_with = "deserialize_optional_size")]
+        max_upload: Option<ConfigSize>,
+    }
+    let config: Config = toml::from_str(r#"max_upload = """#).unwrap();
+    assert!(config.max_upload.is_none());
+    let config: Config = toml::from_str("").unwrap();
+    assert!(config.max_upload.is_none());
+    let config: Config = toml::from_str(r#"max_upload = "5MiB""#).unwrap();
+    assert_eq!(config.max_upload, Some(ConfigSize::new_from_mebibytes(5)));
+}
+
+#[test]
+pub fn test_numeric_limit() {
+    let config: SizeConfig = toml::from_str(
+        r#"
+            max_upload = "10MiB"
+            cache = 1024
+            limit = 536870912
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.limit,
+        ConfigSizeLimit::Limited(ConfigSize::new_from_mebibytes(512))
+    );
+    let serialized = toml::to_string(&config).unwrap();
+    assert!(serialized.contains(r#"limit = "512MiB""#));
+}